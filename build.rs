@@ -0,0 +1,30 @@
+//! Generates the C header `aud2.h` for the ffi feature.
+
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+}
+
+/// Generates `aud2.h` from `src/ffi.rs` with cbindgen, configured by `cbindgen.toml`.
+///
+/// The header is written to `OUT_DIR`. The committed `include/aud2.h` is only overwritten if the environment variable
+/// `AUD2_UPDATE_HEADER` is set, so that a normal build does not change the source tree.
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=AUD2_UPDATE_HEADER");
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("Read cbindgen.toml");
+    let bindings = cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Generate C header");
+    bindings.write_to_file(format!("{}/aud2.h", out_dir));
+    if std::env::var_os("AUD2_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(format!("{}/include/aud2.h", crate_dir));
+    }
+}
@@ -0,0 +1,169 @@
+/* C interface of aud2, see src/ffi.rs. Generated by cbindgen, do not edit. */
+
+#ifndef AUD2_H
+#define AUD2_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Whether a call succeeded.
+typedef enum Aud2Status {
+  // The call succeeded and the result is valid.
+  AUD2_STATUS_OK = 0,
+  // An array was `NULL` although its length is not zero.
+  AUD2_STATUS_NULL_POINTER = 1,
+  // There are more items than the solver supports, e.g. for meet in the middle.
+  AUD2_STATUS_TOO_MANY_ITEMS = 2,
+  // The solver panicked. This is a bug in aud2.
+  AUD2_STATUS_PANIC = 3,
+} Aud2Status;
+
+// The result of a knapsack solver.
+typedef struct Aud2KnapsackResult {
+  // Whether the call succeeded. All other fields are empty if not.
+  enum Aud2Status status;
+  // The id's of the chosen items, `len` entries.
+  size_t *ids;
+  // The number of chosen items.
+  size_t len;
+  // Total profit of the chosen items.
+  uint64_t profit;
+  // Total weight of the chosen items.
+  uint64_t weight;
+} Aud2KnapsackResult;
+
+// The result of the fractional greedy algorithm. Item `ids[i]` is taken `numerators[i] / denominators[i]` times.
+typedef struct Aud2FractionalResult {
+  // Whether the call succeeded. All other fields are empty if not.
+  enum Aud2Status status;
+  // The id's of the (partially) packed items, `len` entries.
+  size_t *ids;
+  // Numerators of the take ratios, `len` entries.
+  uint64_t *numerators;
+  // Denominators of the take ratios, `len` entries.
+  uint64_t *denominators;
+  // The number of (partially) packed items.
+  size_t len;
+} Aud2FractionalResult;
+
+// The result of a subset sum solver.
+typedef struct Aud2SubsetSumResult {
+  // Whether the call succeeded. `reachable` is false if not.
+  enum Aud2Status status;
+  // Whether the sum is reachable.
+  bool reachable;
+} Aud2SubsetSumResult;
+
+// Solves the knapsack with [knapsack::dynamic_programming].
+//
+// # Safety
+//
+// `ids`, `profits` and `weights` must point to `len` valid elements each, or may be `NULL` if `len` is zero.
+struct Aud2KnapsackResult aud2_knapsack_dynamic_programming(const size_t *ids,
+                                                            const uint64_t *profits,
+                                                            const uint64_t *weights,
+                                                            size_t len,
+                                                            uint64_t weight_limit);
+
+// Solves the knapsack with [knapsack::branch_and_bound].
+//
+// # Safety
+//
+// See [aud2_knapsack_dynamic_programming].
+struct Aud2KnapsackResult aud2_knapsack_branch_and_bound(const size_t *ids,
+                                                         const uint64_t *profits,
+                                                         const uint64_t *weights,
+                                                         size_t len,
+                                                         uint64_t weight_limit);
+
+// Solves the knapsack with [knapsack::meet_in_the_middle]. Fails with `AUD2_STATUS_TOO_MANY_ITEMS` for more than
+// [knapsack::MEET_IN_THE_MIDDLE_MAX_ITEMS] items.
+//
+// # Safety
+//
+// See [aud2_knapsack_dynamic_programming].
+struct Aud2KnapsackResult aud2_knapsack_meet_in_the_middle(const size_t *ids,
+                                                           const uint64_t *profits,
+                                                           const uint64_t *weights,
+                                                           size_t len,
+                                                           uint64_t weight_limit);
+
+// Approximates the knapsack with [knapsack::greedy_k].
+//
+// # Safety
+//
+// See [aud2_knapsack_dynamic_programming].
+struct Aud2KnapsackResult aud2_knapsack_greedy_k(const size_t *ids,
+                                                 const uint64_t *profits,
+                                                 const uint64_t *weights,
+                                                 size_t len,
+                                                 uint64_t weight_limit,
+                                                 size_t k);
+
+// Approximates the knapsack with [knapsack::integer_greedy].
+//
+// # Safety
+//
+// See [aud2_knapsack_dynamic_programming].
+struct Aud2KnapsackResult aud2_knapsack_integer_greedy(const size_t *ids,
+                                                       const uint64_t *profits,
+                                                       const uint64_t *weights,
+                                                       size_t len,
+                                                       uint64_t weight_limit);
+
+// Approximates the knapsack with [knapsack::modified_greedy].
+//
+// # Safety
+//
+// See [aud2_knapsack_dynamic_programming].
+struct Aud2KnapsackResult aud2_knapsack_modified_greedy(const size_t *ids,
+                                                        const uint64_t *profits,
+                                                        const uint64_t *weights,
+                                                        size_t len,
+                                                        uint64_t weight_limit);
+
+// Releases the memory owned by `result` and resets it to an empty result. Does nothing if `result` is `NULL`.
+//
+// # Safety
+//
+// `result` must be `NULL` or point to a result returned by one of the `aud2_knapsack_*` functions, which was not
+// modified by the caller.
+void aud2_knapsack_result_free(struct Aud2KnapsackResult *result);
+
+// Solves the fractional knapsack with [knapsack::fractional_greedy].
+//
+// # Safety
+//
+// See [aud2_knapsack_dynamic_programming].
+struct Aud2FractionalResult aud2_knapsack_fractional_greedy(const size_t *ids,
+                                                            const uint64_t *profits,
+                                                            const uint64_t *weights,
+                                                            size_t len,
+                                                            uint64_t weight_limit);
+
+// Releases the memory owned by `result` and resets it to an empty result. Does nothing if `result` is `NULL`.
+//
+// # Safety
+//
+// `result` must be `NULL` or point to a result returned by [aud2_knapsack_fractional_greedy], which was not
+// modified by the caller.
+void aud2_fractional_result_free(struct Aud2FractionalResult *result);
+
+// Decides subset sum with [subset_sum::subset_sum_set].
+//
+// # Safety
+//
+// `numbers` must point to `len` valid elements, or may be `NULL` if `len` is zero.
+struct Aud2SubsetSumResult aud2_subset_sum_set(const uint64_t *numbers, size_t len, uint64_t sum);
+
+// Decides subset sum with [subset_sum::subset_sum_vec].
+//
+// # Safety
+//
+// See [aud2_subset_sum_set].
+struct Aud2SubsetSumResult aud2_subset_sum_vec(const uint64_t *numbers, size_t len, uint64_t sum);
+
+#endif  /* AUD2_H */
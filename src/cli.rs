@@ -1,14 +1,52 @@
 //! Type definitions for command line argument parsing via [argh].
 
+use std::num::NonZeroU128;
+use std::ops::Not;
+use std::str::FromStr;
+
 use argh::FromArgs;
 
+use aud2::generator::InstanceClass;
+use aud2::knapsack::GreedyStrategy;
+
 /// AuD2: Algorithms from "Algorithms and Data Structures 2" implemented in Rust.
 #[derive(FromArgs, PartialEq, Debug)]
 pub(crate) struct CliArgs {
+    /// output format of the results: text, json or csv.
+    #[argh(option, default = "OutputFormat::Text")]
+    pub(crate) output: OutputFormat,
+
     #[argh(subcommand)]
     pub(crate) subcommand: CliCommands,
 }
 
+/// Output formats for the results of all subcommands.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum OutputFormat {
+    /// Human readable text.
+    Text,
+    /// A json document.
+    Json,
+    /// A csv table.
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "unknown output format {:?}, expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
 /// Enum of all subcommands.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand)]
@@ -19,75 +57,130 @@ pub(crate) enum CliCommands {
     KnapsackGreedyK(KnapsackGreedyK),
     SubsetSumRowSumSet(SubsetSumRowSet),
     SubsetSumFullTable(SubsetSumFullTable),
+    SubsetSumBitset(SubsetSumBitset),
+    SubsetSumApproximate(SubsetSumApproximate),
+    NumberPartition(NumberPartition),
+    NumberPartitionKarmarkarKarp(NumberPartitionKarmarkarKarp),
     KnapsackIntegerGreedy(KnapsackIntegerGreedy),
+    KnapsackModifiedGreedy(KnapsackModifiedGreedy),
+    KnapsackMeetInTheMiddle(KnapsackMeetInTheMiddle),
+    GenerateKnapsack(GenerateKnapsack),
+    KnapsackCompare(KnapsackCompare),
+    KnapsackVerify(KnapsackVerify),
+    KnapsackExport(KnapsackExport),
+    KnapsackImportSolution(KnapsackImportSolution),
 }
 
 /// FractionalKnapsack
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "frac-ks")]
 pub(crate) struct KnapsackFractionalGreedy {
-    /// path to a csv file with the input elements (id, weight, profit).
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
     #[argh(option)]
     pub(crate) items_csv: String,
 
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
     /// enable this flag if your CSV is written from left to right.
     #[argh(switch, short = 'f')]
     pub(crate) flipped_csv: bool,
 
-    /// maximum weight of the knapsack.
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
     #[argh(option)]
-    pub(crate) weight_limit: u64,
+    pub(crate) weight_limit: Option<u64>,
 }
 
 /// Solve maximum knapsack with dynamic programming.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "ks-dp")]
 pub(crate) struct KnapsackDynamicProgramming {
-    /// path to a csv file with the input elements (id, weight, profit).
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
     #[argh(option)]
     pub(crate) items_csv: String,
 
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
     /// enable this flag if your CSV is written from left to right.
     #[argh(switch, short = 'f')]
     pub(crate) flipped_csv: bool,
 
-    /// maximum weight of the knapsack.
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// stop solving after this many seconds and print the best knapsack found so far. Its status is then
+    /// deadline_exceeded and it is not guaranteed to be optimal.
     #[argh(option)]
-    pub(crate) weight_limit: u64,
+    pub(crate) time_limit: Option<f64>,
+
+    /// memory budget in MiB. If the normal dynamic programming would need more memory, a variant with memory linear in
+    /// the weight limit is used.
+    #[argh(option)]
+    pub(crate) memory_budget_mib: Option<u64>,
 }
 
 /// Solve maximum knapsack with branch and bound.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "ks-bb")]
 pub(crate) struct KnapsackBranchBound {
-    /// path to a csv file with the input elements (id, weight, profit).
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
     #[argh(option)]
     pub(crate) items_csv: String,
 
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
     /// enable this flag if your CSV is written from left to right.
     #[argh(switch, short = 'f')]
     pub(crate) flipped_csv: bool,
 
-    /// maximum weight of the knapsack.
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
     #[argh(option)]
-    pub(crate) weight_limit: u64,
+    pub(crate) weight_limit: Option<u64>,
+
+    /// stop solving after this many seconds and print the best knapsack found so far. Its status is then
+    /// deadline_exceeded and it is not guaranteed to be optimal.
+    #[argh(option)]
+    pub(crate) time_limit: Option<f64>,
+
+    /// comma seperated list of id's of a known solution to start with, e.g. 1,4,6.
+    #[argh(option, from_str_fn(parse_ids))]
+    pub(crate) initial_solution: Option<Vec<usize>>,
+
+    /// with --initial-solution, also solve without it and print how many nodes the warm start saved.
+    #[argh(switch)]
+    pub(crate) compare_cold: bool,
 }
 
 /// Solve maximum knapsack with the greedy_k approximation algorithm. The result may not be optimal.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "ks-greedyk")]
 pub(crate) struct KnapsackGreedyK {
-    /// path to a csv file with the input elements (id, weight, profit).
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
     #[argh(option)]
     pub(crate) items_csv: String,
 
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
     /// enable this flag if your CSV is written from left to right.
     #[argh(switch, short = 'f')]
     pub(crate) flipped_csv: bool,
 
-    /// maximum weight of the knapsack.
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
     #[argh(option)]
-    pub(crate) weight_limit: u64,
+    pub(crate) weight_limit: Option<u64>,
+
+    /// stop solving after this many seconds and print the best knapsack found so far. Its status is then
+    /// deadline_exceeded and it is not guaranteed to be optimal.
+    #[argh(option)]
+    pub(crate) time_limit: Option<f64>,
 
     /// number of fixed items.
     #[argh(option)]
@@ -98,23 +191,167 @@ pub(crate) struct KnapsackGreedyK {
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "subsum-row")]
 pub(crate) struct SubsetSumRowSet {
-    /// sum that should be reached.
+    /// sum that should be reached, may be negative.
     #[argh(option)]
-    pub(crate) sum: u64,
+    pub(crate) sum: i64,
 
-    /// comma seperated list of numbers.
+    /// print the number of subsets reaching the sum instead of one of them.
+    #[argh(switch)]
+    pub(crate) count: bool,
+
+    /// with --count, print the number of subsets modulo this number. Requires --count.
+    #[argh(option)]
+    pub(crate) modulus: Option<NonZeroU128>,
+
+    /// print a subset with the largest sum not exceeding the sum, if it is not reachable exactly.
+    #[argh(switch)]
+    pub(crate) at_most: bool,
+
+    /// print a subset with the sum nearest to the sum, below or above it.
+    #[argh(switch)]
+    pub(crate) nearest: bool,
+
+    /// use at least this many numbers.
+    #[argh(option)]
+    pub(crate) min_count: Option<usize>,
+
+    /// use at most this many numbers. Set --min-count to the same value to use exactly this many numbers.
+    #[argh(option)]
+    pub(crate) max_count: Option<usize>,
+
+    /// read the numbers from this file instead, or from stdin if it is -. The numbers are separated by newlines or
+    /// commas, or the file is a csv file with a number and a label in each line and an optional header "number,label".
+    #[argh(option)]
+    pub(crate) numbers_file: Option<String>,
+
+    /// comma seperated list of numbers. Pass negative numbers after `--`, e.g. `-- 5 -7`.
     #[argh(positional)]
-    pub(crate) numbers: Vec<u64>,
+    pub(crate) numbers: Vec<i64>,
 }
 
 /// Solve subset sum and print a the full bool table of reachable sums.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "subsum-full")]
 pub(crate) struct SubsetSumFullTable {
-    /// sum that should be reached.
+    /// sum that should be reached, may be negative.
+    #[argh(option)]
+    pub(crate) sum: i64,
+
+    /// print the number of subsets reaching the sum instead of one of them.
+    #[argh(switch)]
+    pub(crate) count: bool,
+
+    /// with --count, print the number of subsets modulo this number. Requires --count.
+    #[argh(option)]
+    pub(crate) modulus: Option<NonZeroU128>,
+
+    /// print a subset with the largest sum not exceeding the sum, if it is not reachable exactly.
+    #[argh(switch)]
+    pub(crate) at_most: bool,
+
+    /// print a subset with the sum nearest to the sum, below or above it.
+    #[argh(switch)]
+    pub(crate) nearest: bool,
+
+    /// use at least this many numbers.
+    #[argh(option)]
+    pub(crate) min_count: Option<usize>,
+
+    /// use at most this many numbers. Set --min-count to the same value to use exactly this many numbers.
+    #[argh(option)]
+    pub(crate) max_count: Option<usize>,
+
+    /// read the numbers from this file instead, or from stdin if it is -. The numbers are separated by newlines or
+    /// commas, or the file is a csv file with a number and a label in each line and an optional header "number,label".
+    #[argh(option)]
+    pub(crate) numbers_file: Option<String>,
+
+    /// comma seperated list of numbers. Pass negative numbers after `--`, e.g. `-- 5 -7`.
+    #[argh(positional)]
+    pub(crate) numbers: Vec<i64>,
+}
+
+/// Solve subset sum with a bitset of reachable sums, which is faster and smaller than the full table.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "subsum-bitset")]
+pub(crate) struct SubsetSumBitset {
+    /// sum that should be reached, must not be negative.
+    #[argh(option)]
+    pub(crate) sum: i64,
+
+    /// read the numbers from this file instead, or from stdin if it is -. The numbers are separated by newlines or
+    /// commas, or the file is a csv file with a number and a label in each line and an optional header "number,label".
+    #[argh(option)]
+    pub(crate) numbers_file: Option<String>,
+
+    /// also return the numbers summing up to the sum. This keeps a bitset per number, so it needs as many times more
+    /// memory as there are numbers.
+    #[argh(switch)]
+    pub(crate) witness: bool,
+
+    /// comma seperated list of numbers, which must not be negative.
+    #[argh(positional)]
+    pub(crate) numbers: Vec<i64>,
+}
+
+/// Approximate the largest subset sum not exceeding a target, which is fast even for large numbers.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "subsum-approx")]
+pub(crate) struct SubsetSumApproximate {
+    /// sum that should not be exceeded.
     #[argh(option)]
     pub(crate) sum: u64,
 
+    /// relative error: the found sum is at least (1 - epsilon) times the best one.
+    #[argh(option, default = "0.01")]
+    pub(crate) epsilon: f64,
+
+    /// read the numbers from this file instead, or from stdin if it is -. The numbers are separated by newlines or
+    /// commas, or the file is a csv file with a number and a label in each line and an optional header "number,label".
+    #[argh(option)]
+    pub(crate) numbers_file: Option<String>,
+
+    /// comma seperated list of numbers.
+    #[argh(positional)]
+    pub(crate) numbers: Vec<u64>,
+}
+
+/// Split numbers into two parts with sums as equal as possible.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "partition")]
+pub(crate) struct NumberPartition {
+    /// read the numbers from this file instead, or from stdin if it is -. The numbers are separated by newlines or
+    /// commas, or the file is a csv file with a number and a label in each line and an optional header "number,label".
+    #[argh(option)]
+    pub(crate) numbers_file: Option<String>,
+
+    /// comma seperated list of numbers.
+    #[argh(positional)]
+    pub(crate) numbers: Vec<u64>,
+}
+
+/// Split numbers into k parts with the Karmarkar-Karp differencing heuristic or the complete Karmarkar-Karp search.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "partition-kk")]
+pub(crate) struct NumberPartitionKarmarkarKarp {
+    /// number of parts.
+    #[argh(option, short = 'k', default = "2")]
+    pub(crate) parts: usize,
+
+    /// search for the optimal partition with complete Karmarkar-Karp instead of only using the heuristic. Supports at
+    /// most 10 parts.
+    #[argh(switch)]
+    pub(crate) complete: bool,
+
+    /// with --complete, stop the search after this many seconds and print the best partition found so far.
+    #[argh(option)]
+    pub(crate) time_limit: Option<f64>,
+
+    /// read the numbers from this file instead, or from stdin if it is -. The numbers are separated by newlines or
+    /// commas, or the file is a csv file with a number and a label in each line and an optional header "number,label".
+    #[argh(option)]
+    pub(crate) numbers_file: Option<String>,
+
     /// comma seperated list of numbers.
     #[argh(positional)]
     pub(crate) numbers: Vec<u64>,
@@ -124,15 +361,312 @@ pub(crate) struct SubsetSumFullTable {
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "ks-ig")]
 pub(crate) struct KnapsackIntegerGreedy {
-    /// path to a csv file with the input elements (id, weight, profit).
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
+    #[argh(option)]
+    pub(crate) items_csv: String,
+
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
+    /// enable this flag if your CSV is written from left to right.
+    #[argh(switch, short = 'f')]
+    pub(crate) flipped_csv: bool,
+
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// stop solving after this many seconds and print the best knapsack found so far. Its status is then
+    /// deadline_exceeded and it is not guaranteed to be optimal.
+    #[argh(option)]
+    pub(crate) time_limit: Option<f64>,
+
+    /// order in which the items are considered: ratio (weight profit ratio), profit or weight.
+    #[argh(option, default = "GreedyStrategy::WeightProfitRatio")]
+    pub(crate) strategy: GreedyStrategy,
+}
+
+/// Solve maximum knapsack with modified greedy, i.e. the better of integer greedy and the best single item. The result
+/// may not be optimal, but has at least half of the optimal profit.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "ks-mg")]
+pub(crate) struct KnapsackModifiedGreedy {
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
     #[argh(option)]
     pub(crate) items_csv: String,
 
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
     /// enable this flag if your CSV is written from left to right.
     #[argh(switch, short = 'f')]
     pub(crate) flipped_csv: bool,
 
-    /// maximum weight of the knapsack.
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// stop solving after this many seconds and print the best knapsack found so far. Its status is then
+    /// deadline_exceeded and it is not guaranteed to be optimal.
+    #[argh(option)]
+    pub(crate) time_limit: Option<f64>,
+}
+
+/// Solve maximum knapsack with meet in the middle. Suited for few items (at most 40) with huge weights.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "ks-mitm")]
+pub(crate) struct KnapsackMeetInTheMiddle {
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
+    #[argh(option)]
+    pub(crate) items_csv: String,
+
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
     #[argh(option)]
-    pub(crate) weight_limit: u64,
+    pub(crate) format: Option<InstanceFormat>,
+
+    /// enable this flag if your CSV is written from left to right.
+    #[argh(switch, short = 'f')]
+    pub(crate) flipped_csv: bool,
+
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// stop solving after this many seconds and print the best knapsack found so far. Its status is then
+    /// deadline_exceeded and it is not guaranteed to be optimal.
+    #[argh(option)]
+    pub(crate) time_limit: Option<f64>,
+}
+
+/// Generate a random knapsack instance and write it as csv file (id, profit, weight), or as json array of items with
+/// --output json.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "gen-ks")]
+pub(crate) struct GenerateKnapsack {
+    /// instance class: uncorrelated, weakly-correlated, strongly-correlated, inverse-strongly-correlated,
+    /// almost-strongly-correlated, subset-sum or spanner.
+    #[argh(option)]
+    pub(crate) class: InstanceClass,
+
+    /// number of items to generate.
+    #[argh(option, short = 'n')]
+    pub(crate) item_count: usize,
+
+    /// range of the weights.
+    #[argh(option, default = "1000")]
+    pub(crate) range: u64,
+
+    /// seed for the random number generator. The same seed always generates the same instance.
+    #[argh(option, default = "0")]
+    pub(crate) seed: u64,
+
+    /// instance class of the spanner items. Only used for the spanner class.
+    #[argh(option, default = "InstanceClass::StronglyCorrelated")]
+    pub(crate) spanner_base: InstanceClass,
+
+    /// number of spanner items. Only used for the spanner class.
+    #[argh(option, default = "2")]
+    pub(crate) spanner_items: usize,
+
+    /// maximum multiplier of the spanner items. Only used for the spanner class.
+    #[argh(option, default = "10")]
+    pub(crate) spanner_multiplier: u64,
+
+    /// path of the csv (or json) file to write. Prints to stdout if omitted.
+    #[argh(option)]
+    pub(crate) output_csv: Option<String>,
+
+    /// enable this flag to write the CSV from left to right.
+    #[argh(switch, short = 'f')]
+    pub(crate) flipped_csv: bool,
+}
+
+/// Run all applicable maximum knapsack algorithms on the same instance(s) and compare their results.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "ks-compare")]
+pub(crate) struct KnapsackCompare {
+    /// path to a file with the input elements: a csv file (id, weight, profit) or instances in another --format. Each
+    /// instance of the file is compared. Either this or --class is required.
+    #[argh(option)]
+    pub(crate) items_csv: Option<String>,
+
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
+    /// enable this flag if your CSV is written from left to right.
+    #[argh(switch, short = 'f')]
+    pub(crate) flipped_csv: bool,
+
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats and defaults
+    /// to half of the total weight for generated instances.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// number of fixed items for greedy_k.
+    #[argh(option, default = "2")]
+    pub(crate) k: usize,
+
+    /// instance class of the instances to generate instead of reading a csv file.
+    #[argh(option)]
+    pub(crate) class: Option<InstanceClass>,
+
+    /// number of items of each generated instance.
+    #[argh(option, short = 'n', default = "20")]
+    pub(crate) item_count: usize,
+
+    /// range of the weights of generated instances.
+    #[argh(option, default = "1000")]
+    pub(crate) range: u64,
+
+    /// number of instances to generate. The instances use the seeds seed, seed + 1, ...
+    #[argh(option, default = "1")]
+    pub(crate) instances: u64,
+
+    /// seed of the first generated instance.
+    #[argh(option, default = "0")]
+    pub(crate) seed: u64,
+
+    /// stop each exact algorithm after this many seconds and report it as skipped.
+    #[argh(option, default = "10.0")]
+    pub(crate) time_limit: f64,
+
+    /// path of a csv file to export the comparison table to.
+    #[argh(option)]
+    pub(crate) export_csv: Option<String>,
+}
+
+/// Verify a knapsack solution: Check its feasibility and how far it is away from the optimum.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "ks-verify")]
+pub(crate) struct KnapsackVerify {
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
+    #[argh(option)]
+    pub(crate) items_csv: String,
+
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
+    /// enable this flag if your CSV is written from left to right.
+    #[argh(switch, short = 'f')]
+    pub(crate) flipped_csv: bool,
+
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// prove or refute optimality by solving the knapsack with dynamic programming.
+    #[argh(switch)]
+    pub(crate) prove_optimality: bool,
+
+    /// id's of the chosen items.
+    #[argh(positional)]
+    pub(crate) ids: Vec<usize>,
+}
+
+/// Export a knapsack as integer linear program for external MILP solvers.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "ks-export")]
+pub(crate) struct KnapsackExport {
+    /// path to a file with the input elements: a csv file (id, weight, profit) or an instance in another --format.
+    #[argh(option)]
+    pub(crate) items_csv: String,
+
+    /// format of the items file: csv, pisinger, or-library, json or toml. Detected from the file if omitted.
+    #[argh(option)]
+    pub(crate) format: Option<InstanceFormat>,
+
+    /// enable this flag if your CSV is written from left to right.
+    #[argh(switch, short = 'f')]
+    pub(crate) flipped_csv: bool,
+
+    /// maximum weight of the knapsack. Required for csv files, overrides the weight limit of other formats with one
+    /// dimension.
+    #[argh(option)]
+    pub(crate) weight_limit: Option<u64>,
+
+    /// format of the model: lp (CPLEX LP) or mps (free MPS).
+    #[argh(option, default = "IlpFormat::Lp")]
+    pub(crate) model_format: IlpFormat,
+
+    /// path of the model file to write. Prints to stdout if omitted.
+    #[argh(option)]
+    pub(crate) output_file: Option<String>,
+}
+
+/// File formats for integer linear programs.
+#[derive(PartialEq, Debug)]
+pub(crate) enum IlpFormat {
+    Lp,
+    Mps,
+}
+
+impl FromStr for IlpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lp" => Ok(IlpFormat::Lp),
+            "mps" => Ok(IlpFormat::Mps),
+            _ => Err(format!("unknown format {:?}, expected lp or mps", s)),
+        }
+    }
+}
+
+/// File formats for knapsack instances.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum InstanceFormat {
+    /// Our own csv format (id, profit, weight), optionally flipped.
+    Csv,
+    /// See [aud2::knapsack::instance::parse_pisinger].
+    Pisinger,
+    /// See [aud2::knapsack::instance::parse_or_library].
+    OrLibrary,
+    /// See [aud2::knapsack::instance::parse_json].
+    Json,
+    /// See [aud2::knapsack::instance::parse_toml].
+    Toml,
+}
+
+impl FromStr for InstanceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(InstanceFormat::Csv),
+            "pisinger" => Ok(InstanceFormat::Pisinger),
+            "or-library" => Ok(InstanceFormat::OrLibrary),
+            "json" => Ok(InstanceFormat::Json),
+            "toml" => Ok(InstanceFormat::Toml),
+            _ => Err(format!(
+                "unknown format {:?}, expected csv, pisinger, or-library, json or toml",
+                s
+            )),
+        }
+    }
+}
+
+/// Read the solution file of an external MILP solver for a knapsack exported with ks-export and print the chosen id's.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "ks-import-solution")]
+pub(crate) struct KnapsackImportSolution {
+    /// path to the solution file written by the MILP solver.
+    #[argh(positional)]
+    pub(crate) solution_file: String,
+}
+
+/// Parses a comma seperated list of id's.
+fn parse_ids(value: &str) -> Result<Vec<usize>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| id.is_empty().not())
+        .map(|id| {
+            id.parse()
+                .map_err(|err| format!("invalid id {:?}: {}", id, err))
+        })
+        .collect()
 }
@@ -0,0 +1,448 @@
+//! Comparison of all [knapsack] algorithms that solve the maximum knapsack problem on the same instance.
+//!
+//! Each applicable algorithm is run and timed. The results are compared against the optimum found by the exact
+//! algorithms, which must all find the same profit. Algorithms that would take too long or need too much memory for
+//! the instance are skipped.
+
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Not;
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+
+use crate::knapsack::{
+    self, GreedyStrategy, Item, Progress, SolveOptions, SolveResult, SolveStatus,
+};
+
+/// [knapsack::dynamic_programming] is only run if `number of items * (weight_limit + 1)` does not exceed this value.
+/// Each cell of its row stores the chosen items, so its memory grows with the number of items and the weight limit.
+pub const DYNAMIC_PROGRAMMING_MAX_CELLS: u64 = 10_000_000;
+
+/// [knapsack::dynamic_programming_linear_memory] is only run if the weight limit does not exceed this value, because it
+/// allocates two rows with `weight_limit + 1` profits.
+pub const DP_LINEAR_MEMORY_MAX_WEIGHT_LIMIT: u64 = 10_000_000;
+
+/// [knapsack::dynamic_programming_linear_memory] is only run if it computes at most this many cells, i.e.
+/// `2 * number of items * (weight_limit + 1)`.
+pub const DP_LINEAR_MEMORY_MAX_CELLS: u64 = 1_000_000_000;
+
+/// [knapsack::branch_and_bound] is only run for at most this many items, because it may explore all `2^n` nodes. Even
+/// below this, a hard instance may not finish, so it is stopped after the time limit of [compare_algorithms].
+pub const BRANCH_AND_BOUND_MAX_ITEMS: usize = 60;
+
+/// The default time limit for each exact algorithm in [compare_algorithms].
+pub const DEFAULT_EXACT_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+/// [knapsack::greedy_k] is only run if it takes at most this many steps, i.e. the number of combinations of at most k
+/// fixed items times the number of items.
+pub const GREEDY_K_MAX_STEPS: u64 = 100_000_000;
+
+/// The result of running one algorithm on an instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// Name of the algorithm.
+    pub algorithm: String,
+    /// Whether the algorithm always returns an optimal solution.
+    pub exact: bool,
+    /// Ids of the chosen items.
+    pub ids: Vec<usize>,
+    /// Total profit of the chosen items.
+    pub profit: u64,
+    /// Total weight of the chosen items.
+    pub weight: u64,
+    /// Difference between the optimal profit and `profit`. `None` if no exact algorithm could be run or they disagree.
+    pub gap: Option<u64>,
+    /// `profit / optimal profit`. `None` if no exact algorithm could be run or they disagree.
+    pub ratio: Option<f64>,
+    /// How long the algorithm took.
+    pub runtime: Duration,
+    /// A counter for the work done by the algorithm.
+    pub counter: Counter,
+    /// How many bytes the algorithm needed at most. `None` if it only needs memory linear in the number of items.
+    pub memory: Option<u64>,
+}
+
+/// An algorithm that was not run, because the instance is too large for it, or that was stopped after the time limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Skipped {
+    /// Name of the algorithm.
+    pub algorithm: String,
+    /// Why the algorithm was skipped.
+    pub reason: String,
+}
+
+/// The result of [compare_algorithms].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    /// One [Comparison] for each algorithm that was run.
+    pub comparisons: Vec<Comparison>,
+    /// The algorithms that were skipped.
+    pub skipped: Vec<Skipped>,
+    /// The profits of the exact algorithms, if they do not all find the same profit. Then at least one of them is
+    /// wrong, so the optimum, gap and ratio are unknown.
+    pub mismatch: Option<ExactMismatch>,
+}
+
+/// The profits of the exact algorithms, which should all be the same optimal profit, but are not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExactMismatch {
+    /// Name and profit of each exact algorithm that was run.
+    pub profits: Vec<(String, u64)>,
+}
+
+impl fmt::Display for ExactMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the exact algorithms found different profits: {}",
+            self.profits
+                .iter()
+                .map(|(algorithm, profit)| format!("{}={}", algorithm, profit))
+                .join(", ")
+        )
+    }
+}
+
+/// A counter for the work the algorithm actually did, e.g. the number of explored nodes or computed table cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counter {
+    /// What is counted, e.g. `nodes`.
+    pub name: &'static str,
+    /// The counted value.
+    pub value: u64,
+}
+
+/// Runs every applicable algorithm for the maximum knapsack problem on `items`.
+///
+/// Algorithms that would take too long or need too much memory for the instance are skipped, see
+/// [DYNAMIC_PROGRAMMING_MAX_CELLS], [DP_LINEAR_MEMORY_MAX_WEIGHT_LIMIT], [DP_LINEAR_MEMORY_MAX_CELLS],
+/// [BRANCH_AND_BOUND_MAX_ITEMS], [GREEDY_K_MAX_STEPS], [knapsack::MEET_IN_THE_MIDDLE_MAX_ITEMS] and
+/// [knapsack::BRUTE_FORCE_MAX_ITEMS]. Exact algorithms that do not finish within `time_limit` are reported as skipped
+/// as well, since their result is not guaranteed to be optimal.
+///
+/// # Arguments
+///
+/// * `items` - The items of the instance.
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+/// * `k` - Number of fixed items for [knapsack::greedy_k].
+/// * `time_limit` - How long each exact algorithm may run, e.g. [DEFAULT_EXACT_TIME_LIMIT].
+///
+/// # Returns
+///
+/// One [Comparison] for each algorithm that was run, the algorithms that were skipped and an [ExactMismatch] if the
+/// exact algorithms disagree.
+pub fn compare_algorithms(
+    items: &[Item],
+    weight_limit: u64,
+    k: usize,
+    time_limit: Duration,
+) -> ComparisonReport {
+    let item_count = items.len() as u64;
+    let mut comparisons = Vec::new();
+    let mut skipped = Vec::new();
+    let mut skip = |algorithm: &str, reason: String| {
+        log::info!("Skipping {}: {}", algorithm, reason);
+        skipped.push(Skipped {
+            algorithm: algorithm.to_string(),
+            reason,
+        });
+    };
+
+    let cells = item_count.saturating_mul(weight_limit.saturating_add(1));
+    if cells <= DYNAMIC_PROGRAMMING_MAX_CELLS {
+        match run_exact(
+            "dynamic_programming",
+            time_limit,
+            |options, last_progress| {
+                let result =
+                    knapsack::dynamic_programming_with_options(items, weight_limit, options);
+                let rows = progress_done(last_progress.get());
+                let memory =
+                    knapsack::dynamic_programming_memory_estimate(items.len(), weight_limit);
+                (
+                    result,
+                    Counter::new("cells", rows.saturating_mul(weight_limit + 1)),
+                    Some(memory),
+                )
+            },
+        ) {
+            Ok(comparison) => comparisons.push(comparison),
+            Err(reason) => skip("dynamic_programming", reason),
+        }
+    } else {
+        skip(
+            "dynamic_programming",
+            format!("{} cells exceed {}", cells, DYNAMIC_PROGRAMMING_MAX_CELLS),
+        );
+    }
+
+    // The halves are solved recursively, which computes at most twice as many cells as a single table
+    let linear_memory_cells = cells.saturating_mul(2);
+    if weight_limit > DP_LINEAR_MEMORY_MAX_WEIGHT_LIMIT {
+        skip(
+            "dp_linear_memory",
+            format!(
+                "weight limit {} exceeds {}",
+                weight_limit, DP_LINEAR_MEMORY_MAX_WEIGHT_LIMIT
+            ),
+        );
+    } else if linear_memory_cells > DP_LINEAR_MEMORY_MAX_CELLS {
+        skip(
+            "dp_linear_memory",
+            format!(
+                "{} cells exceed {}",
+                linear_memory_cells, DP_LINEAR_MEMORY_MAX_CELLS
+            ),
+        );
+    } else {
+        match run_exact("dp_linear_memory", time_limit, |options, _| {
+            let (result, statistics) = knapsack::dynamic_programming_linear_memory_with_options(
+                items,
+                weight_limit,
+                options,
+            );
+            (
+                result,
+                Counter::new("cells", statistics.computed_cells),
+                Some(statistics.memory_bytes()),
+            )
+        }) {
+            Ok(comparison) => comparisons.push(comparison),
+            Err(reason) => skip("dp_linear_memory", reason),
+        }
+    }
+
+    if items.len() <= BRANCH_AND_BOUND_MAX_ITEMS {
+        match run_exact("branch_and_bound", time_limit, |options, _| {
+            let (result, statistics) =
+                knapsack::branch_and_bound_with_options(items, weight_limit, options);
+            (
+                result,
+                Counter::new("nodes", statistics.explored_nodes),
+                None,
+            )
+        }) {
+            Ok(comparison) => comparisons.push(comparison),
+            Err(reason) => skip("branch_and_bound", reason),
+        }
+    } else {
+        skip(
+            "branch_and_bound",
+            format!(
+                "{} items exceed {}",
+                items.len(),
+                BRANCH_AND_BOUND_MAX_ITEMS
+            ),
+        );
+    }
+
+    if items.len() <= knapsack::MEET_IN_THE_MIDDLE_MAX_ITEMS {
+        match run_exact("meet_in_the_middle", time_limit, |options, _| {
+            let (result, statistics) =
+                knapsack::meet_in_the_middle_with_options(items, weight_limit, options)
+                    .expect("Number of items was checked before");
+            (
+                result,
+                Counter::new("subsets", statistics.remaining_subsets),
+                Some(statistics.memory_bytes()),
+            )
+        }) {
+            Ok(comparison) => comparisons.push(comparison),
+            Err(reason) => skip("meet_in_the_middle", reason),
+        }
+    } else {
+        skip(
+            "meet_in_the_middle",
+            format!(
+                "{} items exceed {}",
+                items.len(),
+                knapsack::MEET_IN_THE_MIDDLE_MAX_ITEMS
+            ),
+        );
+    }
+
+    if items.len() <= knapsack::BRUTE_FORCE_MAX_ITEMS {
+        match run_exact("brute_force", time_limit, |options, last_progress| {
+            let result = knapsack::brute_force_with_options(items, weight_limit, options)
+                .expect("Number of items was checked before");
+            let subsets = progress_done(last_progress.get());
+            (result, Counter::new("subsets", subsets), None)
+        }) {
+            Ok(comparison) => comparisons.push(comparison),
+            Err(reason) => skip("brute_force", reason),
+        }
+    } else {
+        skip(
+            "brute_force",
+            format!(
+                "{} items exceed {}",
+                items.len(),
+                knapsack::BRUTE_FORCE_MAX_ITEMS
+            ),
+        );
+    }
+
+    let greedy_k = format!("greedy_k (k={})", k);
+    // None if the number of steps does not even fit into an u64
+    let combinations = (0..=k as u64).try_fold(0u64, |combinations, fixed| {
+        combinations.checked_add(knapsack::binomial(item_count, fixed)?)
+    });
+    let greedy_k_steps = combinations.and_then(|combinations| combinations.checked_mul(item_count));
+    match (combinations, greedy_k_steps) {
+        (Some(_), Some(greedy_k_steps)) if greedy_k_steps <= GREEDY_K_MAX_STEPS => {
+            let (comparison, _) = run(&greedy_k, false, None, |options, last_progress| {
+                let result = knapsack::greedy_k_with_options(items, weight_limit, k, options);
+                let combinations = progress_done(last_progress.get());
+                (result, Counter::new("combinations", combinations), None)
+            });
+            comparisons.push(comparison);
+        }
+        (_, Some(greedy_k_steps)) => skip(
+            &greedy_k,
+            format!("{} steps exceed {}", greedy_k_steps, GREEDY_K_MAX_STEPS),
+        ),
+        (_, None) => skip(
+            &greedy_k,
+            format!("more than {} steps exceed {}", u64::MAX, GREEDY_K_MAX_STEPS),
+        ),
+    }
+
+    let (comparison, _) = run("integer_greedy", false, None, |options, last_progress| {
+        let result = knapsack::integer_greedy_with_options(
+            items,
+            weight_limit,
+            GreedyStrategy::WeightProfitRatio,
+            options,
+        );
+        let items = progress_done(last_progress.get());
+        (result, Counter::new("items", items), None)
+    });
+    comparisons.push(comparison);
+
+    let (comparison, _) = run("modified_greedy", false, None, |options, last_progress| {
+        let result = knapsack::modified_greedy_with_options(items, weight_limit, options);
+        let items = progress_done(last_progress.get());
+        (result, Counter::new("items", items), None)
+    });
+    comparisons.push(comparison);
+
+    // All exact algorithms must find the same optimal profit, otherwise at least one of them is wrong
+    let exact_profits: Vec<(String, u64)> = comparisons
+        .iter()
+        .filter(|comparison| comparison.exact)
+        .map(|comparison| (comparison.algorithm.clone(), comparison.profit))
+        .collect();
+    let mismatch = exact_profits
+        .iter()
+        .map(|(_, profit)| profit)
+        .all_equal()
+        .not()
+        .then(|| ExactMismatch {
+            profits: exact_profits.clone(),
+        });
+    if let Some(mismatch) = &mismatch {
+        log::error!("{}", mismatch);
+    }
+
+    // Fill in the gap and approximation ratio, if the optimum is known
+    let optimum = match mismatch {
+        None => exact_profits.first().map(|(_, profit)| *profit),
+        Some(_) => None,
+    };
+    if let Some(optimum) = optimum {
+        for comparison in &mut comparisons {
+            // A profit above the optimum means that the algorithm returned an infeasible knapsack
+            comparison.gap = optimum.checked_sub(comparison.profit);
+            if comparison.gap.is_none() {
+                log::error!(
+                    "{} found profit {}, which exceeds the optimum {}",
+                    comparison.algorithm,
+                    comparison.profit,
+                    optimum
+                );
+            }
+            comparison.ratio = Some(if optimum == 0 {
+                1.0
+            } else {
+                comparison.profit as f64 / optimum as f64
+            });
+        }
+    }
+    ComparisonReport {
+        comparisons,
+        skipped,
+        mismatch,
+    }
+}
+
+impl Counter {
+    fn new(name: &'static str, value: u64) -> Self {
+        Counter { name, value }
+    }
+}
+
+/// The result of an algorithm run by [run]: The result of the solver, the work it did and how many bytes it needed at
+/// most.
+type Measured<'a> = (SolveResult<'a, Item>, Counter, Option<u64>);
+
+/// Runs and times an exact algorithm like [run], but stops it after `time_limit`. Returns why it is skipped instead of
+/// the [Comparison] if it was stopped, because its knapsack is then not guaranteed to be optimal.
+fn run_exact<'a>(
+    algorithm: &str,
+    time_limit: Duration,
+    solve: impl FnOnce(&SolveOptions, &Cell<Option<Progress>>) -> Measured<'a>,
+) -> Result<Comparison, String> {
+    match run(algorithm, true, Some(Instant::now() + time_limit), solve) {
+        (comparison, SolveStatus::Completed) => Ok(comparison),
+        _ => Err(format!(
+            "not finished within the time limit of {:?}",
+            time_limit
+        )),
+    }
+}
+
+/// Runs and times an algorithm, which is stopped at `deadline` if given. `solve` gets the [SolveOptions] to pass to the
+/// algorithm and the last progress the algorithm reported, from which it can read the work done. Gap and ratio are not
+/// filled in.
+fn run<'a>(
+    algorithm: &str,
+    exact: bool,
+    deadline: Option<Instant>,
+    solve: impl FnOnce(&SolveOptions, &Cell<Option<Progress>>) -> Measured<'a>,
+) -> (Comparison, SolveStatus) {
+    log::info!("Running {}", algorithm);
+    let last_progress = Cell::new(None);
+    let options = SolveOptions {
+        deadline,
+        progress: Some(Box::new(|progress| last_progress.set(Some(progress)))),
+        ..SolveOptions::default()
+    };
+    let start = Instant::now();
+    let (result, counter, memory) = solve(&options, &last_progress);
+    let runtime = start.elapsed();
+    let knapsack = result.knapsack;
+    let comparison = Comparison {
+        algorithm: algorithm.to_string(),
+        exact,
+        ids: knapsack.iter().map(|item| item.id).collect(),
+        profit: knapsack.iter().map(|item| item.profit).sum(),
+        weight: knapsack.iter().map(|item| item.weight).sum(),
+        gap: None,
+        ratio: None,
+        runtime,
+        counter,
+        memory,
+    };
+    (comparison, result.status)
+}
+
+/// How much work is done according to the last reported `progress`, or 0 if nothing was reported.
+fn progress_done(progress: Option<Progress>) -> u64 {
+    match progress {
+        Some(Progress::Rows { done, .. } | Progress::Items { done, .. }) => done as u64,
+        Some(Progress::Combinations { done, .. } | Progress::Subsets { done, .. }) => done,
+        Some(Progress::ExploredNodes(explored_nodes)) => explored_nodes,
+        None => 0,
+    }
+}
@@ -0,0 +1,408 @@
+//! C-compatible interface to the [knapsack](crate::knapsack) and [subset sum](crate::subset_sum) solvers. Only
+//! available with the `ffi` feature.
+//!
+//! The items of a knapsack are passed as three arrays of the same length `len`: their `ids`, `profits` and `weights`.
+//! The solvers return a result struct by value. Results that own memory must be released with the matching free
+//! function, e.g. [aud2_knapsack_result_free]. The header `include/aud2.h` is generated from this module by
+//! [cbindgen](https://github.com/mozilla/cbindgen) when building with the `ffi` feature.
+
+use std::ops::Not;
+use std::panic::{self, UnwindSafe};
+use std::ptr;
+use std::slice;
+
+use crate::knapsack::{self, Item};
+use crate::subset_sum;
+
+/// Whether a call succeeded.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Aud2Status {
+    /// The call succeeded and the result is valid.
+    Ok = 0,
+    /// An array was `NULL` although its length is not zero.
+    NullPointer = 1,
+    /// There are more items than the solver supports, e.g. for meet in the middle.
+    TooManyItems = 2,
+    /// The solver panicked. This is a bug in aud2.
+    Panic = 3,
+}
+
+/// The result of a knapsack solver.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Aud2KnapsackResult {
+    /// Whether the call succeeded. All other fields are empty if not.
+    pub status: Aud2Status,
+    /// The id's of the chosen items, `len` entries.
+    pub ids: *mut usize,
+    /// The number of chosen items.
+    pub len: usize,
+    /// Total profit of the chosen items.
+    pub profit: u64,
+    /// Total weight of the chosen items.
+    pub weight: u64,
+}
+
+/// The result of the fractional greedy algorithm. Item `ids[i]` is taken `numerators[i] / denominators[i]` times.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Aud2FractionalResult {
+    /// Whether the call succeeded. All other fields are empty if not.
+    pub status: Aud2Status,
+    /// The id's of the (partially) packed items, `len` entries.
+    pub ids: *mut usize,
+    /// Numerators of the take ratios, `len` entries.
+    pub numerators: *mut u64,
+    /// Denominators of the take ratios, `len` entries.
+    pub denominators: *mut u64,
+    /// The number of (partially) packed items.
+    pub len: usize,
+}
+
+/// The result of a subset sum solver.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Aud2SubsetSumResult {
+    /// Whether the call succeeded. `reachable` is false if not.
+    pub status: Aud2Status,
+    /// Whether the sum is reachable.
+    pub reachable: bool,
+}
+
+/// Solves the knapsack with [knapsack::dynamic_programming].
+///
+/// # Safety
+///
+/// `ids`, `profits` and `weights` must point to `len` valid elements each, or may be `NULL` if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_dynamic_programming(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+) -> Aud2KnapsackResult {
+    solve_knapsack(ids, profits, weights, len, |items| {
+        Ok(knapsack::dynamic_programming(items, weight_limit))
+    })
+}
+
+/// Solves the knapsack with [knapsack::branch_and_bound].
+///
+/// # Safety
+///
+/// See [aud2_knapsack_dynamic_programming].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_branch_and_bound(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+) -> Aud2KnapsackResult {
+    solve_knapsack(ids, profits, weights, len, |items| {
+        Ok(knapsack::branch_and_bound(items, weight_limit))
+    })
+}
+
+/// Solves the knapsack with [knapsack::meet_in_the_middle]. Fails with `AUD2_STATUS_TOO_MANY_ITEMS` for more than
+/// [knapsack::MEET_IN_THE_MIDDLE_MAX_ITEMS] items.
+///
+/// # Safety
+///
+/// See [aud2_knapsack_dynamic_programming].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_meet_in_the_middle(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+) -> Aud2KnapsackResult {
+    solve_knapsack(ids, profits, weights, len, |items| {
+        knapsack::meet_in_the_middle(items, weight_limit).map_err(|_| Aud2Status::TooManyItems)
+    })
+}
+
+/// Approximates the knapsack with [knapsack::greedy_k].
+///
+/// # Safety
+///
+/// See [aud2_knapsack_dynamic_programming].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_greedy_k(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+    k: usize,
+) -> Aud2KnapsackResult {
+    solve_knapsack(ids, profits, weights, len, |items| {
+        Ok(knapsack::greedy_k(items, weight_limit, k))
+    })
+}
+
+/// Approximates the knapsack with [knapsack::integer_greedy].
+///
+/// # Safety
+///
+/// See [aud2_knapsack_dynamic_programming].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_integer_greedy(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+) -> Aud2KnapsackResult {
+    solve_knapsack(ids, profits, weights, len, |items| {
+        Ok(knapsack::integer_greedy(items, weight_limit))
+    })
+}
+
+/// Approximates the knapsack with [knapsack::modified_greedy].
+///
+/// # Safety
+///
+/// See [aud2_knapsack_dynamic_programming].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_modified_greedy(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+) -> Aud2KnapsackResult {
+    solve_knapsack(ids, profits, weights, len, |items| {
+        Ok(knapsack::modified_greedy(items, weight_limit))
+    })
+}
+
+/// Releases the memory owned by `result` and resets it to an empty result. Does nothing if `result` is `NULL`.
+///
+/// # Safety
+///
+/// `result` must be `NULL` or point to a result returned by one of the `aud2_knapsack_*` functions, which was not
+/// modified by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_result_free(result: *mut Aud2KnapsackResult) {
+    if let Some(result) = result.as_mut() {
+        free_array(result.ids, result.len);
+        result.ids = ptr::null_mut();
+        result.len = 0;
+    }
+}
+
+/// Solves the fractional knapsack with [knapsack::fractional_greedy].
+///
+/// # Safety
+///
+/// See [aud2_knapsack_dynamic_programming].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_knapsack_fractional_greedy(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    weight_limit: u64,
+) -> Aud2FractionalResult {
+    let empty = |status| Aud2FractionalResult {
+        status,
+        ids: ptr::null_mut(),
+        numerators: ptr::null_mut(),
+        denominators: ptr::null_mut(),
+        len: 0,
+    };
+    let items = match read_items(ids, profits, weights, len) {
+        Ok(items) => items,
+        Err(status) => return empty(status),
+    };
+    let knapsack = catch_panic(|| {
+        knapsack::fractional_greedy(&items, weight_limit)
+            .iter()
+            .map(|packed_item| {
+                (
+                    packed_item.item.id,
+                    *packed_item.take_ratio.numer().unwrap_or(&0),
+                    *packed_item.take_ratio.denom().unwrap_or(&1),
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    match knapsack {
+        Ok(knapsack) => Aud2FractionalResult {
+            status: Aud2Status::Ok,
+            ids: into_array(knapsack.iter().map(|&(id, _, _)| id).collect()),
+            numerators: into_array(knapsack.iter().map(|&(_, numer, _)| numer).collect()),
+            denominators: into_array(knapsack.iter().map(|&(_, _, denom)| denom).collect()),
+            len: knapsack.len(),
+        },
+        Err(status) => empty(status),
+    }
+}
+
+/// Releases the memory owned by `result` and resets it to an empty result. Does nothing if `result` is `NULL`.
+///
+/// # Safety
+///
+/// `result` must be `NULL` or point to a result returned by [aud2_knapsack_fractional_greedy], which was not
+/// modified by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn aud2_fractional_result_free(result: *mut Aud2FractionalResult) {
+    if let Some(result) = result.as_mut() {
+        free_array(result.ids, result.len);
+        free_array(result.numerators, result.len);
+        free_array(result.denominators, result.len);
+        result.ids = ptr::null_mut();
+        result.numerators = ptr::null_mut();
+        result.denominators = ptr::null_mut();
+        result.len = 0;
+    }
+}
+
+/// Decides subset sum with [subset_sum::subset_sum_set].
+///
+/// # Safety
+///
+/// `numbers` must point to `len` valid elements, or may be `NULL` if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn aud2_subset_sum_set(
+    numbers: *const u64,
+    len: usize,
+    sum: u64,
+) -> Aud2SubsetSumResult {
+    solve_subset_sum(numbers, len, |numbers| {
+        subset_sum::subset_sum_set(numbers, sum)
+    })
+}
+
+/// Decides subset sum with [subset_sum::subset_sum_vec].
+///
+/// # Safety
+///
+/// See [aud2_subset_sum_set].
+#[no_mangle]
+pub unsafe extern "C" fn aud2_subset_sum_vec(
+    numbers: *const u64,
+    len: usize,
+    sum: u64,
+) -> Aud2SubsetSumResult {
+    solve_subset_sum(numbers, len, |numbers| {
+        subset_sum::subset_sum_vec(numbers, sum)
+    })
+}
+
+// Helper functions
+
+/// Reads the items from the arrays `ids`, `profits` and `weights` and runs `solver` on them.
+unsafe fn solve_knapsack<F>(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+    solver: F,
+) -> Aud2KnapsackResult
+where
+    F: FnOnce(&[Item]) -> Result<Vec<&Item>, Aud2Status> + UnwindSafe,
+{
+    let empty = |status| Aud2KnapsackResult {
+        status,
+        ids: ptr::null_mut(),
+        len: 0,
+        profit: 0,
+        weight: 0,
+    };
+    let items = match read_items(ids, profits, weights, len) {
+        Ok(items) => items,
+        Err(status) => return empty(status),
+    };
+    // The totals are summed inside catch_panic as well, since an overflow panics with overflow checks enabled
+    let result = catch_panic(|| {
+        solver(&items).map(|knapsack| {
+            let profit: u64 = knapsack.iter().map(|item| item.profit).sum();
+            let weight: u64 = knapsack.iter().map(|item| item.weight).sum();
+            let ids: Vec<usize> = knapsack.iter().map(|item| item.id).collect();
+            (ids, profit, weight)
+        })
+    });
+    match result.and_then(|result| result) {
+        Ok((ids, profit, weight)) => Aud2KnapsackResult {
+            status: Aud2Status::Ok,
+            len: ids.len(),
+            profit,
+            weight,
+            ids: into_array(ids),
+        },
+        Err(status) => empty(status),
+    }
+}
+
+/// Reads `numbers` and runs `solver` on them.
+unsafe fn solve_subset_sum<F>(numbers: *const u64, len: usize, solver: F) -> Aud2SubsetSumResult
+where
+    F: FnOnce(&[u64]) -> bool + UnwindSafe,
+{
+    let result = read_array(numbers, len).and_then(|numbers| catch_panic(|| solver(numbers)));
+    match result {
+        Ok(reachable) => Aud2SubsetSumResult {
+            status: Aud2Status::Ok,
+            reachable,
+        },
+        Err(status) => Aud2SubsetSumResult {
+            status,
+            reachable: false,
+        },
+    }
+}
+
+/// Combines the arrays `ids`, `profits` and `weights` to items.
+unsafe fn read_items(
+    ids: *const usize,
+    profits: *const u64,
+    weights: *const u64,
+    len: usize,
+) -> Result<Vec<Item>, Aud2Status> {
+    let ids = read_array(ids, len)?;
+    let profits = read_array(profits, len)?;
+    let weights = read_array(weights, len)?;
+    Ok((0..len)
+        .map(|index| Item {
+            id: ids[index],
+            profit: profits[index],
+            weight: weights[index],
+        })
+        .collect())
+}
+
+/// Creates a slice of the array `array` with `len` elements.
+unsafe fn read_array<'a, T>(array: *const T, len: usize) -> Result<&'a [T], Aud2Status> {
+    if len == 0 {
+        Ok(&[])
+    } else if array.is_null() {
+        Err(Aud2Status::NullPointer)
+    } else {
+        Ok(slice::from_raw_parts(array, len))
+    }
+}
+
+/// Hands `values` over to C. The array must be released by [free_array] with the same length.
+fn into_array<T>(values: Vec<T>) -> *mut T {
+    if values.is_empty() {
+        return ptr::null_mut();
+    }
+    Box::into_raw(values.into_boxed_slice()) as *mut T
+}
+
+/// Releases an array created by [into_array].
+unsafe fn free_array<T>(array: *mut T, len: usize) {
+    if array.is_null().not() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(array, len)));
+    }
+}
+
+/// Runs `f` and converts a panic into [Aud2Status::Panic], because panics must not unwind into C.
+fn catch_panic<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, Aud2Status> {
+    panic::catch_unwind(f).map_err(|_| Aud2Status::Panic)
+}
@@ -0,0 +1,239 @@
+//! Generation of random [knapsack](crate::knapsack) instances in the instance classes described by David Pisinger in
+//! ["Where are the hard knapsack problems?"](https://doi.org/10.1016/j.cor.2004.03.002).
+//!
+//! All instances are generated from a seed, so the same seed always results in the same instance.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::knapsack::Item;
+
+/// The instance classes by Pisinger. They differ in how the profit of an item is correlated to its weight. `R` denotes
+/// the range of the weights.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InstanceClass {
+    /// Weight and profit are chosen independently from `[1, R]`.
+    Uncorrelated,
+    /// Weight is chosen from `[1, R]`, profit from `[weight - R/10, weight + R/10]`.
+    WeaklyCorrelated,
+    /// Weight is chosen from `[1, R]`, profit is `weight + R/10`.
+    StronglyCorrelated,
+    /// Profit is chosen from `[1, R]`, weight is `profit + R/10`.
+    InverseStronglyCorrelated,
+    /// Weight is chosen from `[1, R]`, profit from `[weight + R/10 - R/500, weight + R/10 + R/500]`.
+    AlmostStronglyCorrelated,
+    /// Weight is chosen from `[1, R]`, profit is equal to the weight.
+    SubsetSum,
+    /// A few spanner items are generated according to `base` and scaled down by `max_multiplier + 1`. Each item is a
+    /// randomly chosen spanner item multiplied with a random multiplier from `[1, max_multiplier]`.
+    Spanner {
+        /// Instance class of the spanner items. Must not be a spanner class itself.
+        base: Box<InstanceClass>,
+        /// How many spanner items are generated.
+        spanner_items: usize,
+        /// Maximum multiplier for the spanner items.
+        max_multiplier: u64,
+    },
+}
+
+impl InstanceClass {
+    /// Generates a single item with the given `id` according to this instance class.
+    fn generate_item(&self, id: usize, range: u64, rng: &mut ChaCha8Rng) -> Item {
+        let range = range.max(1);
+        let (profit, weight) = match self {
+            InstanceClass::Uncorrelated => (rng.gen_range(1..=range), rng.gen_range(1..=range)),
+            InstanceClass::WeaklyCorrelated => {
+                let weight = rng.gen_range(1..=range);
+                let deviation = range / 10;
+                let profit =
+                    rng.gen_range(weight.saturating_sub(deviation).max(1)..=weight + deviation);
+                (profit, weight)
+            }
+            InstanceClass::StronglyCorrelated => {
+                let weight = rng.gen_range(1..=range);
+                (weight + range / 10, weight)
+            }
+            InstanceClass::InverseStronglyCorrelated => {
+                let profit = rng.gen_range(1..=range);
+                (profit, profit + range / 10)
+            }
+            InstanceClass::AlmostStronglyCorrelated => {
+                let weight = rng.gen_range(1..=range);
+                let profit = weight + range / 10;
+                let deviation = range / 500;
+                (
+                    rng.gen_range(profit - deviation..=profit + deviation),
+                    weight,
+                )
+            }
+            InstanceClass::SubsetSum => {
+                let weight = rng.gen_range(1..=range);
+                (weight, weight)
+            }
+            InstanceClass::Spanner { .. } => {
+                unreachable!("generate rejects spanner classes with a spanner base")
+            }
+        };
+        Item { id, profit, weight }
+    }
+}
+
+impl FromStr for InstanceClass {
+    type Err = String;
+
+    /// Parses the name of an instance class. `spanner` results in strongly correlated spanner instances with 2 spanner
+    /// items and a maximum multiplier of 10.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uncorrelated" => Ok(InstanceClass::Uncorrelated),
+            "weakly-correlated" => Ok(InstanceClass::WeaklyCorrelated),
+            "strongly-correlated" => Ok(InstanceClass::StronglyCorrelated),
+            "inverse-strongly-correlated" => Ok(InstanceClass::InverseStronglyCorrelated),
+            "almost-strongly-correlated" => Ok(InstanceClass::AlmostStronglyCorrelated),
+            "subset-sum" => Ok(InstanceClass::SubsetSum),
+            "spanner" => Ok(InstanceClass::Spanner {
+                base: Box::new(InstanceClass::StronglyCorrelated),
+                spanner_items: 2,
+                max_multiplier: 10,
+            }),
+            _ => Err(format!(
+                "unknown instance class {:?}, expected one of uncorrelated, weakly-correlated, \
+                strongly-correlated, inverse-strongly-correlated, almost-strongly-correlated, subset-sum, spanner",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for InstanceClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InstanceClass::Uncorrelated => "uncorrelated",
+            InstanceClass::WeaklyCorrelated => "weakly-correlated",
+            InstanceClass::StronglyCorrelated => "strongly-correlated",
+            InstanceClass::InverseStronglyCorrelated => "inverse-strongly-correlated",
+            InstanceClass::AlmostStronglyCorrelated => "almost-strongly-correlated",
+            InstanceClass::SubsetSum => "subset-sum",
+            InstanceClass::Spanner { .. } => "spanner",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Error returned by [generate] if the parameters of a [InstanceClass::Spanner] class are invalid.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SpannerError {
+    /// The base class is a spanner class itself.
+    NestedSpanner,
+    /// `spanner_items` is 0, so there is no spanner item to build the items from.
+    NoSpannerItems,
+    /// `max_multiplier` is 0, so there is no multiplier in `[1, max_multiplier]`.
+    NoMultipliers,
+}
+
+impl fmt::Display for SpannerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SpannerError::NestedSpanner => {
+                "the base class of a spanner class must not be a spanner class itself"
+            }
+            SpannerError::NoSpannerItems => "a spanner class needs at least one spanner item",
+            SpannerError::NoMultipliers => {
+                "the maximum multiplier of a spanner class must be at least 1"
+            }
+        })
+    }
+}
+
+impl std::error::Error for SpannerError {}
+
+/// Generates a random knapsack instance.
+///
+/// # Arguments
+///
+/// * `class` - The instance class, which determines how profits and weights are correlated.
+/// * `item_count` - How many items should be generated. The items get the ids `1..=item_count`.
+/// * `range` - The range `R` of the weights (or of the profits for [InstanceClass::InverseStronglyCorrelated]).
+/// * `seed` - Seed for the random number generator. The same seed always results in the same items.
+///
+/// # Returns
+///
+/// The generated items, or a [SpannerError] if `class` is a spanner class with a spanner base, no spanner items or a
+/// maximum multiplier of 0.
+///
+/// # Examples
+///
+/// ```
+/// # use aud2::generator::{generate, InstanceClass};
+/// let items = generate(&InstanceClass::SubsetSum, 10, 1000, 42).unwrap();
+/// assert_eq!(items.len(), 10);
+/// assert!(items.iter().all(|item| item.profit == item.weight));
+/// assert_eq!(items, generate(&InstanceClass::SubsetSum, 10, 1000, 42).unwrap());
+/// ```
+pub fn generate(
+    class: &InstanceClass,
+    item_count: usize,
+    range: u64,
+    seed: u64,
+) -> Result<Vec<Item>, SpannerError> {
+    if let InstanceClass::Spanner {
+        base,
+        spanner_items,
+        max_multiplier,
+    } = class
+    {
+        if let InstanceClass::Spanner { .. } = **base {
+            return Err(SpannerError::NestedSpanner);
+        }
+        if *spanner_items == 0 {
+            return Err(SpannerError::NoSpannerItems);
+        }
+        if *max_multiplier == 0 {
+            return Err(SpannerError::NoMultipliers);
+        }
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let items: Vec<Item> = match class {
+        InstanceClass::Spanner {
+            base,
+            spanner_items,
+            max_multiplier,
+        } => {
+            // Generate the spanner items and normalize them by dividing through max_multiplier + 1
+            let spanner_items: Vec<Item> = (0..*spanner_items)
+                .map(|i| base.generate_item(i, range, &mut rng))
+                .map(|item| Item {
+                    id: item.id,
+                    profit: item.profit.div_ceil(max_multiplier + 1),
+                    weight: item.weight.div_ceil(max_multiplier + 1),
+                })
+                .collect();
+            log::debug!("Spanner items: {:?}", spanner_items);
+            // Each item is a multiple of a random spanner item
+            (1..=item_count)
+                .map(|id| {
+                    let spanner_item = &spanner_items[rng.gen_range(0..spanner_items.len())];
+                    let multiplier = rng.gen_range(1..=*max_multiplier);
+                    Item {
+                        id,
+                        profit: spanner_item.profit * multiplier,
+                        weight: spanner_item.weight * multiplier,
+                    }
+                })
+                .collect()
+        }
+        _ => (1..=item_count)
+            .map(|id| class.generate_item(id, range, &mut rng))
+            .collect(),
+    };
+    log::info!(
+        "Generated {} {} items with total weight {}",
+        items.len(),
+        class,
+        items.iter().map(|item| item.weight).sum::<u64>()
+    );
+    Ok(items)
+}
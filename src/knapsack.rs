@@ -4,21 +4,38 @@
 //! in a collection so that the total weight is less than or equal to a given limit and the total value is as large as
 //! possible"
 
+// Lints of the original algorithms, which are kept as they were written
+#![allow(
+    suspicious_double_ref_op,
+    clippy::doc_lazy_continuation,
+    clippy::map_flatten,
+    clippy::non_canonical_partial_ord_impl
+)]
+
 use std::borrow::Borrow;
-use std::cmp::Ordering;
+use std::cell::Cell;
+use std::cmp::{Ordering, Reverse};
 use std::fmt;
+use std::mem;
 use std::ops::{Deref, Not};
+use std::str::FromStr;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::Arc;
+use std::time::Instant;
 
 use fraction::Fraction;
 use itertools::Itertools;
 use log::log_enabled;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
+
+pub mod ilp;
+pub mod instance;
 
 // ------- Item ----------------------------------
 
 /// An item is an object that has a profit and weight. An item can be put into a knapsack, which causes the item to be
 /// wrapped in an [PartialPackedItem].
-#[derive(Eq, PartialEq, Clone, Deserialize)]
+#[derive(Eq, PartialEq, Clone, Deserialize, Serialize)]
 pub struct Item {
     /// An unique identifier.
     pub id: usize,
@@ -67,7 +84,9 @@ impl fmt::Debug for Item {
 // ------- PartialPackedItem ----------------------------------
 
 /// An [Item] that was put inside a knapsack, storing how much of the item was put into the knapsack.
-#[derive(Debug, PartialEq, Clone)]
+///
+/// When serialized, `take_ratio` is written as string like `"3/4"` to keep it exact.
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct PartialPackedItem<'a, ItemRef>
 where
     ItemRef: Borrow<Item>,
@@ -75,6 +94,7 @@ where
     /// The original item.
     pub item: &'a ItemRef,
     /// A fraction indicating how much of the item was put into the knapsack.
+    #[serde(serialize_with = "serialize_fraction")]
     pub take_ratio: Fraction,
 }
 
@@ -93,6 +113,56 @@ where
     }
 }
 
+/// A solved knapsack: The chosen items and their totals. Unlike the `Vec<&ItemRef>` returned by the solvers, it owns
+/// its items, so it can be stored and serialized.
+///
+/// ```
+/// # use aud2::knapsack::{dynamic_programming, Item, Solution};
+/// let items = [
+///     Item { id: 0, profit: 6, weight: 2 },
+///     Item { id: 1, profit: 5, weight: 3 },
+/// ];
+/// let solution = Solution::new(dynamic_programming(&items, 4), 4);
+/// assert_eq!(solution.ids(), vec![0]);
+/// assert_eq!((solution.profit, solution.weight), (6, 2));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Solution {
+    /// The chosen items.
+    pub items: Vec<Item>,
+    /// Total profit of the chosen items.
+    pub profit: u64,
+    /// Total weight of the chosen items.
+    pub weight: u64,
+    /// The maximum allowed weight of the knapsack.
+    pub weight_limit: u64,
+}
+
+impl Solution {
+    /// Creates the solution of the knapsack with the chosen items `knapsack` and the weight limit `weight_limit`.
+    pub fn new<'a, ItemRef, ItemIter>(knapsack: ItemIter, weight_limit: u64) -> Self
+    where
+        ItemRef: 'a + Borrow<Item>,
+        ItemIter: IntoIterator<Item = &'a ItemRef>,
+    {
+        let items: Vec<Item> = knapsack
+            .into_iter()
+            .map(|item| item.borrow().clone())
+            .collect();
+        Solution {
+            profit: items.iter().map(|item| item.profit).sum(),
+            weight: items.iter().map(|item| item.weight).sum(),
+            items,
+            weight_limit,
+        }
+    }
+
+    /// Returns the id's of the chosen items.
+    pub fn ids(&self) -> Vec<usize> {
+        self.items.iter().map(|item| item.id).collect()
+    }
+}
+
 // Allow items to be compared by their weight_profit_ration.
 impl PartialOrd for Item {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -108,6 +178,150 @@ impl Ord for Item {
     }
 }
 
+// ------- Solve Options ----------------------------------
+
+/// A token to cancel a running solver from another thread. Clones of a token share the same state, so cancelling one
+/// clone cancels all of them.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests all solvers using this token to stop as soon as possible.
+    pub fn cancel(&self) {
+        self.0.store(true, atomic::Ordering::Relaxed);
+    }
+
+    /// Whether [CancellationToken::cancel] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(atomic::Ordering::Relaxed)
+    }
+}
+
+/// Progress of a running solver, passed to [SolveOptions::progress].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Progress {
+    /// [dynamic_programming] calculated `done` of `total` rows.
+    Rows { done: usize, total: usize },
+    /// [dynamic_programming_linear_memory], [integer_greedy] or [modified_greedy] decided for `done` of `total` items
+    /// whether they are taken.
+    Items { done: usize, total: usize },
+    /// [branch_and_bound] or [crate::partition::complete_karmarkar_karp] explored so many nodes of the search tree.
+    ExploredNodes(u64),
+    /// [greedy_k] tried `done` of `total` combinations of fixed items.
+    Combinations { done: u64, total: u64 },
+    /// [brute_force] tried `done` of `total` subsets, or [meet_in_the_middle] merged `done` of `total` subsets of the
+    /// first half with the second half.
+    Subsets { done: u64, total: u64 },
+}
+
+impl Progress {
+    /// How much of the work is done in percent, if the total amount of work is known.
+    pub fn percent(&self) -> Option<f64> {
+        let (done, total) = match *self {
+            Progress::Rows { done, total } | Progress::Items { done, total } => {
+                (done as f64, total as f64)
+            }
+            Progress::Combinations { done, total } | Progress::Subsets { done, total } => {
+                (done as f64, total as f64)
+            }
+            Progress::ExploredNodes(_) => return None,
+        };
+        Some(if total == 0.0 {
+            100.0
+        } else {
+            100.0 * done / total
+        })
+    }
+}
+
+/// Options to control a running solver, accepted by the `*_with_options` variants of all solvers of the maximum knapsack
+/// problem.
+///
+/// [fractional_greedy] has no such variant: It solves the fractional knapsack problem, whose packed items do not fit
+/// into a [SolveResult], and only sorts the items once, so it finishes about as fast as a solver could check for a stop.
+#[derive(Default)]
+pub struct SolveOptions<'a> {
+    /// Stop solving once this token is cancelled.
+    pub cancellation_token: Option<CancellationToken>,
+    /// Stop solving once this point in time is reached.
+    pub deadline: Option<Instant>,
+    /// Called regularly with the progress of the solver.
+    pub progress: Option<Box<dyn Fn(Progress) + 'a>>,
+}
+
+impl<'a> SolveOptions<'a> {
+    /// Returns why the solver should stop, or `None` if it should continue.
+    pub(crate) fn stop_status(&self) -> Option<SolveStatus> {
+        if let Some(cancellation_token) = &self.cancellation_token {
+            if cancellation_token.is_cancelled() {
+                return Some(SolveStatus::Cancelled);
+            }
+        }
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => Some(SolveStatus::DeadlineExceeded),
+            _ => None,
+        }
+    }
+
+    /// Passes `progress` to the progress callback, if there is one.
+    pub(crate) fn report(&self, progress: Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+}
+
+impl<'a> fmt::Debug for SolveOptions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolveOptions")
+            .field("cancellation_token", &self.cancellation_token)
+            .field("deadline", &self.deadline)
+            .field("progress", &self.progress.as_ref().map(|_| "Fn(Progress)"))
+            .finish()
+    }
+}
+
+/// Whether a solver ran until the end.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SolveStatus {
+    /// The solver ran until the end.
+    Completed,
+    /// The solver was stopped by [SolveOptions::cancellation_token].
+    Cancelled,
+    /// The solver was stopped because [SolveOptions::deadline] was reached.
+    DeadlineExceeded,
+}
+
+/// The result of a solver that accepts [SolveOptions].
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolveResult<'a, ItemRef> {
+    /// The best knapsack found. If the solver was stopped early, this is the best knapsack known so far.
+    pub knapsack: Vec<&'a ItemRef>,
+    /// Whether the solver ran until the end.
+    pub status: SolveStatus,
+    /// Whether the knapsack is guaranteed to be optimal. This is only the case for exact solvers that ran until the
+    /// end.
+    pub optimal: bool,
+}
+
+impl<'a, ItemRef> SolveResult<'a, ItemRef> {
+    fn new(knapsack: Vec<&'a ItemRef>, status: SolveStatus, exact: bool) -> Self {
+        if status != SolveStatus::Completed {
+            log::info!("Solver stopped early: {:?}", status);
+        }
+        SolveResult {
+            knapsack,
+            status,
+            optimal: exact && status == SolveStatus::Completed,
+        }
+    }
+}
+
 // ------- Solving Algorithms ----------------------------------
 
 /// Solves the [fractional knapsack problem](https://en.wikipedia.org/wiki/Continuous_knapsack_problem) by using the
@@ -218,6 +432,22 @@ where
     ItemIter: IntoIterator<Item = &'a ItemRef>,
     ItemRef: Borrow<Item>,
 {
+    dynamic_programming_with_options(items, weight_capacity, &SolveOptions::default()).knapsack
+}
+
+/// Same as [dynamic_programming], but can be cancelled and reports its progress via [SolveOptions]. If stopped early,
+/// the returned knapsack is the optimal knapsack for the items processed so far.
+pub fn dynamic_programming_with_options<'a, ItemIter, ItemRef>(
+    items: ItemIter,
+    weight_capacity: u64,
+    options: &SolveOptions,
+) -> SolveResult<'a, ItemRef>
+where
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+    ItemRef: Borrow<Item>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+
     // Row stores the current row. Each cell contains a list of items, which are included in the knapsack. This takes
     // the first item_nr items into account and the knapsack limited by the weight specified by the index of the cell.
     let mut row: Vec<Vec<&ItemRef>> = vec![Vec::new(); (weight_capacity + 1) as usize];
@@ -226,7 +456,11 @@ where
     log::debug!("weight_limits={:?}", (0..row.len()).collect_vec());
 
     // Examine which profits are producible by using a new item from the item list.
-    for (item_nr, item) in items.into_iter().enumerate() {
+    for (item_nr, &item) in items.iter().enumerate() {
+        if let Some(status) = options.stop_status() {
+            return SolveResult::new(row.pop().unwrap_or_default(), status, true);
+        }
+
         // Create the new row by inspecting the old one and inspect if improvement can be made by using the new item.
         // Because we override the old row, we go from right ro left.
         for index in (0..row.len()).rev() {
@@ -319,12 +553,23 @@ where
                 .collect();
             log::log!(profits_log_level, "Row i={}: {:?}", item_nr, row_profits);
         }
+        options.report(Progress::Rows {
+            done: item_nr + 1,
+            total: items.len(),
+        });
     }
-    row.pop().unwrap_or_default()
+    SolveResult::new(row.pop().unwrap_or_default(), SolveStatus::Completed, true)
 }
 
 /// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with
-/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming). The returned solution may not be optimal!
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming), but only needs memory linear in the
+/// weight limit. The returned solution is optimal.
+///
+/// Instead of storing the chosen items for each cell, only the profits of a single row are stored. The chosen items
+/// are reconstructed by divide and conquer like in
+/// [Hirschberg's algorithm](https://en.wikipedia.org/wiki/Hirschberg%27s_algorithm): The items are split into two
+/// halves, the rows of both halves are calculated and combined to find out how much of the weight limit the optimal
+/// knapsack spends on each half. Then both halves are solved recursively.
 ///
 /// # Arguments
 ///
@@ -335,6 +580,235 @@ where
 /// # Returns
 ///
 /// The knapsack, i.e. all items that are chosen to be in the knapsack.
+pub fn dynamic_programming_linear_memory<'a, ItemIter, ItemRef>(
+    items: ItemIter,
+    weight_limit: u64,
+) -> Vec<&'a ItemRef>
+where
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+    ItemRef: Borrow<Item>,
+{
+    dynamic_programming_linear_memory_with_options(items, weight_limit, &SolveOptions::default())
+        .0
+        .knapsack
+}
+
+/// Statistics about the rows calculated by [dynamic_programming_linear_memory_with_options].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct LinearMemoryStatistics {
+    /// How many cells of all rows were calculated, summed over all recursive calls.
+    pub computed_cells: u64,
+    /// The largest number of cells of the two rows that were allocated at the same time.
+    pub peak_row_cells: u64,
+}
+
+impl LinearMemoryStatistics {
+    /// How many bytes the rows needed at most.
+    pub fn memory_bytes(&self) -> u64 {
+        self.peak_row_cells
+            .saturating_mul(mem::size_of::<u64>() as u64)
+    }
+}
+
+/// Same as [dynamic_programming_linear_memory], but can be cancelled and reports its progress via [SolveOptions]. If
+/// stopped early, the returned knapsack contains only the items chosen so far, so it is feasible but may be far from
+/// optimal. Additionally returns statistics about the calculated rows.
+pub fn dynamic_programming_linear_memory_with_options<'a, ItemIter, ItemRef>(
+    items: ItemIter,
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> (SolveResult<'a, ItemRef>, LinearMemoryStatistics)
+where
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+    ItemRef: Borrow<Item>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    let mut solver = LinearMemorySolver {
+        options,
+        item_count: items.len(),
+        decided_items: 0,
+        knapsack: Vec::new(),
+        statistics: LinearMemoryStatistics::default(),
+    };
+    let status = match solver.solve(&items, weight_limit) {
+        Ok(()) => SolveStatus::Completed,
+        Err(status) => status,
+    };
+    (
+        SolveResult::new(solver.knapsack, status, true),
+        solver.statistics,
+    )
+}
+
+/// State of [dynamic_programming_linear_memory_with_options] shared by all recursive calls.
+struct LinearMemorySolver<'a, 'o, 'p, ItemRef> {
+    options: &'o SolveOptions<'p>,
+    item_count: usize,
+    /// For how many items it is already decided whether they are taken.
+    decided_items: usize,
+    /// The items chosen so far.
+    knapsack: Vec<&'a ItemRef>,
+    statistics: LinearMemoryStatistics,
+}
+
+impl<'a, ItemRef> LinearMemorySolver<'a, '_, '_, ItemRef>
+where
+    ItemRef: Borrow<Item>,
+{
+    /// Solves the knapsack for `items` and `weight_limit` and appends the chosen items to `knapsack`. Returns the
+    /// status if the solver was stopped.
+    fn solve(&mut self, items: &[&'a ItemRef], weight_limit: u64) -> Result<(), SolveStatus> {
+        match items {
+            [] => {}
+            [item] => {
+                // A single item is taken if it fits
+                let item_ref: &Item = (*item).borrow();
+                if item_ref.weight <= weight_limit && item_ref.profit > 0 {
+                    self.knapsack.push(item);
+                }
+                self.decided_items += 1;
+                self.options.report(Progress::Items {
+                    done: self.decided_items,
+                    total: self.item_count,
+                });
+            }
+            _ => {
+                let (first_half, second_half) = items.split_at(items.len() / 2);
+                let first_row = self.profit_row(first_half, weight_limit)?;
+                let second_row = self.profit_row(second_half, weight_limit)?;
+                // Both rows are allocated at the same time
+                self.statistics.peak_row_cells = self
+                    .statistics
+                    .peak_row_cells
+                    .max((first_row.len() + second_row.len()) as u64);
+                // Find the best split of the weight limit between both halves
+                let first_weight_limit = (0..first_row.len())
+                    .max_by_key(|&weight| {
+                        first_row[weight] + second_row[first_row.len() - 1 - weight]
+                    })
+                    .expect("Rows have at least one cell");
+                log::debug!(
+                    "Splitting {} items into halves with weight limits {} and {} for profit {}",
+                    items.len(),
+                    first_weight_limit,
+                    weight_limit - first_weight_limit as u64,
+                    first_row[first_weight_limit]
+                        + second_row[first_row.len() - 1 - first_weight_limit]
+                );
+                // Both rows are no longer needed, so free their memory before recursing
+                drop((first_row, second_row));
+                self.solve(first_half, first_weight_limit as u64)?;
+                self.solve(second_half, weight_limit - first_weight_limit as u64)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls [dynamic_programming_profit_row] and counts its cells in the statistics.
+    fn profit_row(
+        &mut self,
+        items: &[&'a ItemRef],
+        weight_limit: u64,
+    ) -> Result<Vec<u64>, SolveStatus> {
+        let row = dynamic_programming_profit_row(items, weight_limit, self.options)?;
+        self.statistics.computed_cells += items.len() as u64 * row.len() as u64;
+        Ok(row)
+    }
+}
+
+/// Calculates the last row of the dynamic programming table, i.e. for each weight limit from `0` to `weight_limit` the
+/// maximum profit reachable with `items`. Returns the status if the solver should be stopped.
+fn dynamic_programming_profit_row<ItemRef>(
+    items: &[&ItemRef],
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> Result<Vec<u64>, SolveStatus>
+where
+    ItemRef: Borrow<Item>,
+{
+    let mut row: Vec<u64> = vec![0; (weight_limit + 1) as usize];
+    for item in items {
+        if let Some(status) = options.stop_status() {
+            return Err(status);
+        }
+        let item: &Item = (*item).borrow();
+        // Because we override the old row, we go from right ro left.
+        for index in (item.weight as usize..row.len()).rev() {
+            row[index] = row[index].max(row[index - item.weight as usize] + item.profit);
+        }
+    }
+    Ok(row)
+}
+
+/// Estimates how many bytes [dynamic_programming] needs at most. Each of the `weight_limit + 1` cells of its row
+/// stores a list of up to `item_count` item references.
+pub fn dynamic_programming_memory_estimate(item_count: usize, weight_limit: u64) -> u64 {
+    let cell_bytes = mem::size_of::<Vec<&Item>>() + item_count * mem::size_of::<&Item>();
+    (weight_limit + 1).saturating_mul(cell_bytes as u64)
+}
+
+/// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming) within a memory budget. The returned
+/// solution is optimal.
+///
+/// If [dynamic_programming] would need more than `memory_budget` bytes according to
+/// [dynamic_programming_memory_estimate], [dynamic_programming_linear_memory] is used instead.
+pub fn dynamic_programming_with_memory_budget<'a, ItemIter, ItemRef>(
+    items: ItemIter,
+    weight_limit: u64,
+    memory_budget: u64,
+) -> Vec<&'a ItemRef>
+where
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+    ItemRef: Borrow<Item>,
+{
+    dynamic_programming_with_memory_budget_with_options(
+        items,
+        weight_limit,
+        memory_budget,
+        &SolveOptions::default(),
+    )
+    .knapsack
+}
+
+/// Same as [dynamic_programming_with_memory_budget], but can be cancelled and reports its progress via [SolveOptions]
+/// like [dynamic_programming_with_options] or [dynamic_programming_linear_memory_with_options], whichever is used.
+pub fn dynamic_programming_with_memory_budget_with_options<'a, ItemIter, ItemRef>(
+    items: ItemIter,
+    weight_limit: u64,
+    memory_budget: u64,
+    options: &SolveOptions,
+) -> SolveResult<'a, ItemRef>
+where
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+    ItemRef: Borrow<Item>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    let memory_estimate = dynamic_programming_memory_estimate(items.len(), weight_limit);
+    if memory_estimate > memory_budget {
+        log::info!(
+            "Using linear memory dynamic programming, because {} bytes exceed the memory budget of {} bytes",
+            memory_estimate,
+            memory_budget
+        );
+        dynamic_programming_linear_memory_with_options(items, weight_limit, options).0
+    } else {
+        dynamic_programming_with_options(items, weight_limit, options)
+    }
+}
+
+/// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming). The returned solution may not be optimal!
+///
+/// # Arguments
+///
+/// * `items` - Something that can be turned into an iterator yielding references to [Item]s or something that can be
+///   borrowed as [Item].
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+///
+/// # Returns
+///
+/// The knapsack, i.e. all items that are chosen to be in the knapsack.
 pub fn integer_greedy<'a, ItemRef, ItemIter>(
     items: ItemIter,
     weight_capacity: u64,
@@ -343,11 +817,75 @@ where
     ItemRef: Borrow<Item>,
     ItemIter: IntoIterator<Item = &'a ItemRef>,
 {
-    // Sort items ascending according to their weight profit ratio. This causes valuable elements to be at the front
-    // and not so valuable elements at the back.
+    integer_greedy_with_strategy(items, weight_capacity, GreedyStrategy::WeightProfitRatio)
+}
+
+/// The order in which [integer_greedy_with_strategy] considers the items.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum GreedyStrategy {
+    /// Ascending by [Item::weight_profit_ratio], i.e. the most valuable items per weight first. This is the order
+    /// used by [integer_greedy].
+    #[default]
+    WeightProfitRatio,
+    /// Descending by profit, i.e. the most profitable items first.
+    Profit,
+    /// Ascending by weight, i.e. the lightest items first.
+    Weight,
+}
+
+impl FromStr for GreedyStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ratio" => Ok(GreedyStrategy::WeightProfitRatio),
+            "profit" => Ok(GreedyStrategy::Profit),
+            "weight" => Ok(GreedyStrategy::Weight),
+            _ => Err(format!(
+                "unknown greedy strategy {:?}, expected one of ratio, profit, weight",
+                s
+            )),
+        }
+    }
+}
+
+/// Same as [integer_greedy], but the order in which the items are considered is determined by `strategy`.
+/// The returned solution may not be optimal!
+pub fn integer_greedy_with_strategy<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_capacity: u64,
+    strategy: GreedyStrategy,
+) -> Vec<&'a ItemRef>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    integer_greedy_with_options(items, weight_capacity, strategy, &SolveOptions::default()).knapsack
+}
+
+/// Same as [integer_greedy_with_strategy], but can be cancelled and reports its progress via [SolveOptions]. If stopped
+/// early, the returned knapsack contains the items taken so far.
+pub fn integer_greedy_with_options<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_capacity: u64,
+    strategy: GreedyStrategy,
+    options: &SolveOptions,
+) -> SolveResult<'a, ItemRef>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    // Sort items according to the strategy. This causes (hopefully) valuable elements to be at the front and not so
+    // valuable elements at the back.
     let items_sorted_asc: Vec<&ItemRef> = {
         let mut items = Vec::from_iter(items);
-        items.sort_by_key(|item| <ItemRef as Borrow<Item>>::borrow(item));
+        match strategy {
+            GreedyStrategy::WeightProfitRatio => {
+                items.sort_by_key(|item| <ItemRef as Borrow<Item>>::borrow(item))
+            }
+            GreedyStrategy::Profit => items.sort_by_key(|item| Reverse((*item).borrow().profit)),
+            GreedyStrategy::Weight => items.sort_by_key(|item| (*item).borrow().weight),
+        }
         items
     };
 
@@ -369,6 +907,14 @@ where
     let mut knapsack: Vec<&ItemRef> = Vec::new();
 
     for (item_index, new_item) in items_sorted_asc.iter().enumerate() {
+        if let Some(status) = options.stop_status() {
+            return SolveResult::new(knapsack, status, false);
+        }
+        options.report(Progress::Items {
+            done: item_index,
+            total: items_sorted_asc.len(),
+        });
+
         // Calculate already used weight, remaining available weight and the currently reached profit
         let used_knapsack_weight: u64 = knapsack
             .iter()
@@ -402,7 +948,78 @@ where
         log::debug!("Taking item id={:<2}", new_item.deref().borrow().id);
         knapsack.push(new_item);
     }
-    knapsack
+    options.report(Progress::Items {
+        done: items_sorted_asc.len(),
+        total: items_sorted_asc.len(),
+    });
+    SolveResult::new(knapsack, SolveStatus::Completed, false)
+}
+
+/// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with the modified greedy
+/// algorithm. It takes the better of the [integer_greedy] knapsack and the most profitable single item that fits.
+/// The returned solution may not be optimal, but has at least half of the optimal profit.
+///
+/// # Arguments
+///
+/// * `items` - Something that can be turned into an iterator yielding references to [Item]s or something that can be
+///   borrowed as [Item].
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+///
+/// # Returns
+///
+/// The knapsack, i.e. all items that are chosen to be in the knapsack.
+pub fn modified_greedy<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+) -> Vec<&'a ItemRef>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    modified_greedy_with_options(items, weight_limit, &SolveOptions::default()).knapsack
+}
+
+/// Same as [modified_greedy], but can be cancelled and reports the progress of its [integer_greedy] via
+/// [SolveOptions]. If stopped early, the returned knapsack contains the items taken by [integer_greedy] so far.
+pub fn modified_greedy_with_options<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> SolveResult<'a, ItemRef>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    let greedy_result = integer_greedy_with_options(
+        items.iter().copied(),
+        weight_limit,
+        GreedyStrategy::WeightProfitRatio,
+        options,
+    );
+    if greedy_result.status != SolveStatus::Completed {
+        return greedy_result;
+    }
+    let greedy_knapsack = greedy_result.knapsack;
+
+    // The greedy knapsack plus the first item that did not fit is more profitable than the optimum (that is what the
+    // fractional greedy would take). So either the greedy knapsack or the item that did not fit has at least half of
+    // the optimal profit.
+    let best_single_item = items
+        .iter()
+        .copied()
+        .filter(|&item| item.borrow().weight <= weight_limit)
+        .max_by_key(|&item| item.borrow().profit);
+    match best_single_item {
+        Some(item) if item.borrow().profit > knapsack_profit(&greedy_knapsack) => {
+            log::debug!(
+                "Best single item id={} is better than the greedy knapsack",
+                item.borrow().id
+            );
+            SolveResult::new(vec![item], SolveStatus::Completed, false)
+        }
+        _ => SolveResult::new(greedy_knapsack, SolveStatus::Completed, false),
+    }
 }
 
 /// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with
@@ -428,12 +1045,51 @@ pub fn greedy_k<'a, ItemRef, ItemIter>(
 where
     ItemRef: 'a + Borrow<Item>,
     // &'a ItemRef: Borrow<Item>,
+    ItemIter: ?Sized,
+    &'a ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    greedy_k_with_options(items, weight_limit, k, &SolveOptions::default()).knapsack
+}
+
+/// Same as [greedy_k], but can be cancelled and reports its progress via [SolveOptions]. If stopped early, the
+/// returned knapsack is the best knapsack of the combinations tried so far.
+pub fn greedy_k_with_options<'a, ItemRef, ItemIter>(
+    items: &'a ItemIter,
+    weight_limit: u64,
+    k: usize,
+    options: &SolveOptions,
+) -> SolveResult<'a, ItemRef>
+where
+    ItemRef: 'a + Borrow<Item>,
+    ItemIter: ?Sized,
     &'a ItemIter: IntoIterator<Item = &'a ItemRef>,
 {
-    (0..=k)
+    let item_count = items.into_iter().count() as u64;
+    let total_combinations = (0..=k as u64)
+        .map(|fixed| binomial(item_count, fixed).unwrap_or(u64::MAX))
+        .fold(0u64, u64::saturating_add);
+    let tried_combinations = Cell::new(0u64);
+    let status = Cell::new(SolveStatus::Completed);
+
+    let knapsack = (0..=k)
         // Get all combinations with 0 elements fixed, 1 element fixed, 2 elements fixed, ..., k elements fixed
         .map(|k_| Itertools::combinations(items.into_iter(), k_))
         .flatten()
+        // Stop if the solver should be stopped
+        .take_while(|_| match options.stop_status() {
+            Some(stop_status) => {
+                status.set(stop_status);
+                false
+            }
+            None => true,
+        })
+        .inspect(|_| {
+            tried_combinations.set(tried_combinations.get() + 1);
+            options.report(Progress::Combinations {
+                done: tried_combinations.get(),
+                total: total_combinations,
+            });
+        })
         .inspect(|fixed_items| {
             log::info!("-- New round --");
             let fixed_weight = fixed_items
@@ -517,7 +1173,8 @@ where
         // Get the best knapsack, i.e. the selection with the most profit
         .max_by_key(|knapsack| knapsack_profit(knapsack))
         // Get either the result or an empty vec
-        .unwrap_or_default()
+        .unwrap_or_default();
+    SolveResult::new(knapsack, status.get(), false)
 }
 
 /// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with
@@ -539,30 +1196,168 @@ pub fn branch_and_bound<'a, ItemRef, ItemIter>(
 ) -> Vec<&'a ItemRef>
 where
     ItemRef: 'a + Borrow<Item>,
+    ItemIter: ?Sized,
     &'a ItemIter: IntoIterator<Item = &'a ItemRef>,
 {
-    // Sort items ascending according to their weight profit ratio. This causes valuable elements to be at the front
-    // and not so valuable elements at the back.
-    let items_sorted: Vec<&ItemRef> = {
-        let mut items = Vec::from_iter(items);
-        items.sort_by_key(|item| <ItemRef as Borrow<Item>>::borrow(item));
-        items
-    };
+    branch_and_bound_with_statistics(items, weight_limit).0
+}
 
-    branch_and_bound_recursive(&items_sorted, weight_limit, &[], &[])
+/// Statistics about the enumeration tree of [branch_and_bound_with_statistics].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct BranchAndBoundStatistics {
+    /// How many nodes of the enumeration tree were visited.
+    pub explored_nodes: u64,
+    /// How many of the explored nodes were skipped together with their subtree, because their upper bound was not
+    /// better than the best known profit.
+    pub pruned_nodes: u64,
 }
 
-/// This function recursively calls itself and performs the main logic of the branch and bound knapsack.
-///
-/// # Arguments
-///
-/// * items - List of objects that can be borrowed as an [Item]. [Items] which should be excluded may not be included in
-/// this list. It is assumed that the items are already sorted. The trick is that this is a reference, so that this
-/// function is able to iterate over items multiple times.
+/// Same as [branch_and_bound], but additionally returns statistics about the explored enumeration tree.
+pub fn branch_and_bound_with_statistics<'a, ItemRef, ItemIter>(
+    items: &'a ItemIter,
+    weight_limit: u64,
+) -> (Vec<&'a ItemRef>, BranchAndBoundStatistics)
+where
+    ItemRef: 'a + Borrow<Item>,
+    ItemIter: ?Sized,
+    &'a ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    let (result, statistics) =
+        branch_and_bound_with_options(items, weight_limit, &SolveOptions::default());
+    (result.knapsack, statistics)
+}
+
+/// Same as [branch_and_bound_with_statistics], but can be cancelled and reports its progress via [SolveOptions]. If
+/// stopped early, the returned knapsack is the best knapsack found so far.
+pub fn branch_and_bound_with_options<'a, ItemRef, ItemIter>(
+    items: &'a ItemIter,
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> (SolveResult<'a, ItemRef>, BranchAndBoundStatistics)
+where
+    ItemRef: 'a + Borrow<Item>,
+    ItemIter: ?Sized,
+    &'a ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    branch_and_bound_from(Vec::from_iter(items), weight_limit, Vec::new(), options)
+}
+
+/// Error returned by [branch_and_bound_with_incumbent] if the initial incumbent is not feasible.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InfeasibleIncumbentError(pub Vec<Infeasibility>);
+
+impl fmt::Display for InfeasibleIncumbentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "initial incumbent is not feasible: ")?;
+        f.write_str(&self.0.iter().map(ToString::to_string).join(", "))
+    }
+}
+
+impl std::error::Error for InfeasibleIncumbentError {}
+
+/// Same as [branch_and_bound_with_options], but starts with an initial incumbent instead of an empty knapsack. A good
+/// incumbent, e.g. a previous solution or the result of [greedy_k], allows pruning subtrees early.
+///
+/// # Arguments
+///
+/// * `items` - List of objects that can be borrowed as an [Item].
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+/// * `incumbent_ids` - The ids of the items of the initial incumbent. The incumbent is checked with [verify] first.
+/// * `options` - Options to stop the solver and report progress.
+///
+/// # Returns
+///
+/// The knapsack and statistics about the explored enumeration tree, or an [InfeasibleIncumbentError] if the incumbent
+/// is not feasible.
+pub fn branch_and_bound_with_incumbent<'a, ItemRef, ItemIter>(
+    items: &'a ItemIter,
+    weight_limit: u64,
+    incumbent_ids: &[usize],
+    options: &SolveOptions,
+) -> Result<(SolveResult<'a, ItemRef>, BranchAndBoundStatistics), InfeasibleIncumbentError>
+where
+    ItemRef: 'a + Borrow<Item>,
+    ItemIter: ?Sized,
+    &'a ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    let verification = verify(items.iter().copied(), weight_limit, incumbent_ids, false);
+    if verification.is_feasible().not() {
+        return Err(InfeasibleIncumbentError(verification.infeasibilities));
+    }
+    log::info!("Starting with incumbent of profit={}", verification.profit);
+    Ok(branch_and_bound_from(
+        items,
+        weight_limit,
+        verification.knapsack,
+        options,
+    ))
+}
+
+/// Sorts the items and starts the branch and bound with `incumbent` as best known knapsack.
+fn branch_and_bound_from<'a, ItemRef>(
+    items: Vec<&'a ItemRef>,
+    weight_limit: u64,
+    incumbent: Vec<&'a ItemRef>,
+    options: &SolveOptions,
+) -> (SolveResult<'a, ItemRef>, BranchAndBoundStatistics)
+where
+    ItemRef: Borrow<Item>,
+{
+    // Sort items ascending according to their weight profit ratio. This causes valuable elements to be at the front
+    // and not so valuable elements at the back.
+    let items_sorted: Vec<&ItemRef> = {
+        let mut items = items;
+        items.sort_by_key(|item| <ItemRef as Borrow<Item>>::borrow(item));
+        items
+    };
+
+    let mut context = BranchAndBoundContext {
+        options,
+        statistics: BranchAndBoundStatistics::default(),
+        status: SolveStatus::Completed,
+    };
+    let knapsack =
+        branch_and_bound_recursive(&items_sorted, weight_limit, &[], &incumbent, &mut context);
+    // The recursion returns an empty knapsack for a leaf, so make sure the incumbent is not lost
+    let knapsack = if knapsack_profit(&incumbent) > knapsack_profit(&knapsack) {
+        incumbent
+    } else {
+        knapsack
+    };
+    log::info!(
+        "Explored {} nodes, pruned {} nodes",
+        context.statistics.explored_nodes,
+        context.statistics.pruned_nodes
+    );
+    (
+        SolveResult::new(knapsack, context.status, true),
+        context.statistics,
+    )
+}
+
+/// State shared by all calls of [branch_and_bound_recursive].
+struct BranchAndBoundContext<'o, 'p> {
+    /// Options to stop the solver and report progress.
+    options: &'o SolveOptions<'p>,
+    /// Statistics about the enumeration tree, which are updated while exploring it.
+    statistics: BranchAndBoundStatistics,
+    /// Whether the solver was stopped.
+    status: SolveStatus,
+}
+
+/// This function recursively calls itself and performs the main logic of the branch and bound knapsack.
+///
+/// # Arguments
+///
+/// * items - List of objects that can be borrowed as an [Item]. [Items] which should be excluded may not be included in
+/// this list. It is assumed that the items are already sorted. The trick is that this is a reference, so that this
+/// function is able to iterate over items multiple times.
 /// * weight_limit - The currently remaining weight limit. This includes weight consumes by earlier decisions
 /// about whether items should be included or excluded.
 /// * fixed_items - Items which are fixed, i.e. always included.
 /// * best_profit - The currently best known profit.
+/// * context - Options, statistics and status of the solver. Once the solver is stopped, no more nodes are explored.
 ///
 /// # Returns
 ///
@@ -572,10 +1367,24 @@ fn branch_and_bound_recursive<'a, 'b, ItemRef>(
     weight_limit: u64,
     fixed_items: &'b [&'a ItemRef],
     best_knapsack: &'b [&'a ItemRef],
+    context: &mut BranchAndBoundContext,
 ) -> Vec<&'a ItemRef>
 where
     ItemRef: Borrow<Item>,
 {
+    // Stop exploring if the solver should be stopped
+    if context.status == SolveStatus::Completed {
+        if let Some(status) = context.options.stop_status() {
+            context.status = status;
+        }
+    }
+    if context.status != SolveStatus::Completed {
+        return best_knapsack.to_vec();
+    }
+    context.statistics.explored_nodes += 1;
+    context
+        .options
+        .report(Progress::ExploredNodes(context.statistics.explored_nodes));
     let mut best_knapsack: Vec<&ItemRef> = best_knapsack.to_vec();
 
     // First, calculate the lower bound. Then, update best_knapsack, if lower bound is an improvement
@@ -617,6 +1426,7 @@ where
             upper_bound_profit,
             knapsack_profit(&best_knapsack)
         );
+        context.statistics.pruned_nodes += 1;
         return best_knapsack;
     }
 
@@ -629,7 +1439,7 @@ where
     // Calculate the reachable profit if we exclude the first item
     log::info!("Exclude item id={}", first.deref().borrow().id);
     let knapsack_exclude_first =
-        branch_and_bound_recursive(tail, weight_limit, fixed_items, &best_knapsack);
+        branch_and_bound_recursive(tail, weight_limit, fixed_items, &best_knapsack, context);
     // Update best_knapsack if a better knapsack was found in the excluding subtree
     if knapsack_profit(&knapsack_exclude_first) > knapsack_profit(&best_knapsack) {
         best_knapsack = knapsack_exclude_first;
@@ -649,6 +1459,7 @@ where
             weight_limit - first.deref().borrow().weight,
             &fixed_items_with_first,
             &best_knapsack,
+            context,
         )
     } else {
         // weight_limit would be negative, which is not allowed
@@ -663,6 +1474,711 @@ where
     best_knapsack
 }
 
+/// Maximum number of items [meet_in_the_middle] accepts. Each half of the items is enumerated completely, so with 40
+/// items both halves contain about 2^20 subsets.
+pub const MEET_IN_THE_MIDDLE_MAX_ITEMS: usize = 40;
+
+/// Error returned by algorithms that only support a limited number of items, like [meet_in_the_middle] or
+/// [brute_force], if more items are passed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TooManyItemsError {
+    /// The number of items that were passed.
+    pub item_count: usize,
+    /// The maximum number of items the algorithm accepts.
+    pub max_item_count: usize,
+}
+
+impl fmt::Display for TooManyItemsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "got {} items, but at most {} items are supported",
+            self.item_count, self.max_item_count
+        )
+    }
+}
+
+impl std::error::Error for TooManyItemsError {}
+
+/// A subset of one half of the items used by [meet_in_the_middle]. The subset is stored as bitmask over the items of
+/// the half.
+#[derive(Debug, Clone, Copy)]
+struct HalfSubset {
+    weight: u64,
+    profit: u64,
+    mask: u64,
+}
+
+/// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with the meet in the middle
+/// algorithm by Horowitz and Sahni. The returned solution is optimal.
+///
+/// The running time depends only on the number of items and not on the weights, so this algorithm is suited for few
+/// items with huge weights, where [dynamic_programming] would need a gigantic table.
+///
+/// # Arguments
+///
+/// * `items` - Something that can be turned into an iterator yielding references to [Item]s or something that can be
+///   borrowed as [Item]. At most [MEET_IN_THE_MIDDLE_MAX_ITEMS] items are allowed.
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+///
+/// # Returns
+///
+/// The knapsack, i.e. all items that are chosen to be in the knapsack, or a [TooManyItemsError] if there are too many
+/// items.
+pub fn meet_in_the_middle<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+) -> Result<Vec<&'a ItemRef>, TooManyItemsError>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    meet_in_the_middle_with_options(items, weight_limit, &SolveOptions::default())
+        .map(|(result, _)| result.knapsack)
+}
+
+/// Statistics about the subsets enumerated by [meet_in_the_middle_with_options].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct MeetInTheMiddleStatistics {
+    /// How many subsets of both halves were enumerated, i.e. fit into the knapsack.
+    pub enumerated_subsets: u64,
+    /// How many of the enumerated subsets remain for merging after the dominance pruning of the second half.
+    pub remaining_subsets: u64,
+}
+
+impl MeetInTheMiddleStatistics {
+    /// How many bytes the enumerated subsets needed at most.
+    pub fn memory_bytes(&self) -> u64 {
+        self.enumerated_subsets
+            .saturating_mul(mem::size_of::<HalfSubset>() as u64)
+    }
+}
+
+/// Same as [meet_in_the_middle], but can be cancelled and reports the progress of merging both halves via
+/// [SolveOptions]. If stopped while enumerating the subsets of the halves, the returned knapsack is the most profitable
+/// subset of a single half enumerated so far. If stopped while merging, it is the best combination found so far.
+/// Additionally returns statistics about the enumerated subsets.
+pub fn meet_in_the_middle_with_options<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> Result<(SolveResult<'a, ItemRef>, MeetInTheMiddleStatistics), TooManyItemsError>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    if items.len() > MEET_IN_THE_MIDDLE_MAX_ITEMS {
+        return Err(TooManyItemsError {
+            item_count: items.len(),
+            max_item_count: MEET_IN_THE_MIDDLE_MAX_ITEMS,
+        });
+    }
+    let (first_half, second_half) = items.split_at(items.len() / 2);
+
+    // Enumerate all subsets of both halves that do not exceed the weight limit
+    let mut statistics = MeetInTheMiddleStatistics::default();
+    let (mut first_subsets, status) = enumerate_half_subsets(first_half, weight_limit, options);
+    statistics.enumerated_subsets = first_subsets.len() as u64;
+    if status != SolveStatus::Completed {
+        let knapsack = best_half_subset_items(first_half, &first_subsets);
+        return Ok((SolveResult::new(knapsack, status, true), statistics));
+    }
+    let (second_subsets, status) = enumerate_half_subsets(second_half, weight_limit, options);
+    statistics.enumerated_subsets += second_subsets.len() as u64;
+    if status != SolveStatus::Completed {
+        // Each subset of a half fits into the knapsack on its own, so take the best one of both halves
+        let first_knapsack = best_half_subset_items(first_half, &first_subsets);
+        let second_knapsack = best_half_subset_items(second_half, &second_subsets);
+        let knapsack = if knapsack_profit(&first_knapsack) >= knapsack_profit(&second_knapsack) {
+            first_knapsack
+        } else {
+            second_knapsack
+        };
+        return Ok((SolveResult::new(knapsack, status, true), statistics));
+    }
+    log::info!(
+        "Enumerated {} subsets of the first half and {} subsets of the second half",
+        first_subsets.len(),
+        second_subsets.len()
+    );
+
+    // Dominance pruning: A subset of the second half is only useful if every lighter subset has less profit. After
+    // this, the subsets are sorted ascending by weight and profit.
+    let second_subsets = prune_dominated_subsets(second_subsets);
+    statistics.remaining_subsets = (first_subsets.len() + second_subsets.len()) as u64;
+    log::info!(
+        "{} subsets of the second half remain after dominance pruning",
+        second_subsets.len()
+    );
+
+    // Merge with a sorted scan: The heavier a subset of the first half, the less weight remains for the second half.
+    // So if the first half is sorted ascending by weight, the best fitting subset of the second half only moves left.
+    first_subsets.sort_by_key(|subset| subset.weight);
+    let mut best: Option<(HalfSubset, HalfSubset)> = None;
+    let mut second_index = second_subsets.len();
+    let mut status = SolveStatus::Completed;
+    let total_subsets = first_subsets.len() as u64;
+    for (merged_subsets, first_subset) in first_subsets.into_iter().enumerate() {
+        if let Some(stop_status) = options.stop_status() {
+            status = stop_status;
+            break;
+        }
+        options.report(Progress::Subsets {
+            done: merged_subsets as u64,
+            total: total_subsets,
+        });
+        let remaining_weight = weight_limit - first_subset.weight;
+        // The empty subset of the second half has weight 0 and always fits, so second_index never drops to 0
+        while second_subsets[second_index - 1].weight > remaining_weight {
+            second_index -= 1;
+        }
+        // Thanks to the dominance pruning, the heaviest fitting subset is also the most profitable one
+        let second_subset = second_subsets[second_index - 1];
+        let profit = first_subset.profit + second_subset.profit;
+        let improves = match best {
+            Some((first, second)) => profit > first.profit + second.profit,
+            None => true,
+        };
+        if improves {
+            log::debug!(
+                "New best combination with weight={} profit={}",
+                first_subset.weight + second_subset.weight,
+                profit
+            );
+            best = Some((first_subset, second_subset));
+        }
+    }
+    if status == SolveStatus::Completed {
+        options.report(Progress::Subsets {
+            done: total_subsets,
+            total: total_subsets,
+        });
+    }
+
+    // Reconstruct the chosen items from the bitmasks
+    let (first_subset, second_subset) = match best {
+        Some(best) => best,
+        None => return Ok((SolveResult::new(Vec::new(), status, true), statistics)),
+    };
+    let knapsack = first_half
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| first_subset.mask & (1 << i) != 0)
+        .chain(
+            second_half
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| second_subset.mask & (1 << i) != 0),
+        )
+        .map(|(_, &item)| item)
+        .collect();
+    Ok((SolveResult::new(knapsack, status, true), statistics))
+}
+
+/// Enumerates all subsets of `items` whose weight does not exceed `weight_limit`. If the solver should be stopped, only
+/// the subsets enumerated so far are returned together with the status.
+fn enumerate_half_subsets<ItemRef>(
+    items: &[&ItemRef],
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> (Vec<HalfSubset>, SolveStatus)
+where
+    ItemRef: Borrow<Item>,
+{
+    let mut subsets = vec![HalfSubset {
+        weight: 0,
+        profit: 0,
+        mask: 0,
+    }];
+    for (i, item) in items.iter().enumerate() {
+        if let Some(status) = options.stop_status() {
+            return (subsets, status);
+        }
+        let item: &Item = (*item).borrow();
+        // Each already known subset can be extended by the new item, if it still fits into the knapsack
+        let extended_subsets: Vec<HalfSubset> = subsets
+            .iter()
+            .filter_map(|subset| {
+                let weight = subset.weight.checked_add(item.weight)?;
+                if weight > weight_limit {
+                    return None;
+                }
+                Some(HalfSubset {
+                    weight,
+                    profit: subset.profit + item.profit,
+                    mask: subset.mask | (1 << i),
+                })
+            })
+            .collect();
+        subsets.extend(extended_subsets);
+    }
+    (subsets, SolveStatus::Completed)
+}
+
+/// Returns the items of the most profitable of the `subsets` of the half `items`.
+fn best_half_subset_items<'a, ItemRef>(
+    items: &[&'a ItemRef],
+    subsets: &[HalfSubset],
+) -> Vec<&'a ItemRef> {
+    let best = subsets
+        .iter()
+        .max_by_key(|subset| subset.profit)
+        .expect("The empty subset is always enumerated")
+        .mask;
+    items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| best & (1 << i) != 0)
+        .map(|(_, &item)| item)
+        .collect()
+}
+
+/// Removes all subsets for which a lighter (or equally heavy) subset with at least the same profit exists. The
+/// returned subsets are sorted ascending by weight and have strictly increasing profits.
+fn prune_dominated_subsets(mut subsets: Vec<HalfSubset>) -> Vec<HalfSubset> {
+    // Sort ascending by weight and for equal weights descending by profit, so that the best subset of each weight
+    // comes first
+    subsets.sort_by(|a, b| a.weight.cmp(&b.weight).then(b.profit.cmp(&a.profit)));
+    let mut pruned: Vec<HalfSubset> = Vec::with_capacity(subsets.len());
+    for subset in subsets {
+        match pruned.last() {
+            Some(last) if last.profit >= subset.profit => continue,
+            _ => pruned.push(subset),
+        }
+    }
+    pruned
+}
+
+/// Maximum number of items [brute_force] accepts, since it tries all 2^n subsets of the items.
+pub const BRUTE_FORCE_MAX_ITEMS: usize = 24;
+
+/// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) by trying every subset of
+/// the items. The returned solution is optimal.
+///
+/// This is only feasible for very few items, but is so simple that it serves as reference to check the other
+/// algorithms against.
+///
+/// # Arguments
+///
+/// * `items` - Something that can be turned into an iterator yielding references to [Item]s or something that can be
+///   borrowed as [Item]. At most [BRUTE_FORCE_MAX_ITEMS] items are allowed.
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+///
+/// # Returns
+///
+/// The knapsack, i.e. all items that are chosen to be in the knapsack, or a [TooManyItemsError] if there are too many
+/// items.
+pub fn brute_force<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+) -> Result<Vec<&'a ItemRef>, TooManyItemsError>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    brute_force_with_options(items, weight_limit, &SolveOptions::default())
+        .map(|result| result.knapsack)
+}
+
+/// Same as [brute_force], but can be cancelled and reports its progress via [SolveOptions]. If stopped early, the
+/// returned knapsack is the best knapsack of the subsets tried so far.
+pub fn brute_force_with_options<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+    options: &SolveOptions,
+) -> Result<SolveResult<'a, ItemRef>, TooManyItemsError>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    if items.len() > BRUTE_FORCE_MAX_ITEMS {
+        return Err(TooManyItemsError {
+            item_count: items.len(),
+            max_item_count: BRUTE_FORCE_MAX_ITEMS,
+        });
+    }
+    let total_subsets = 1u64 << items.len();
+    let mut status = SolveStatus::Completed;
+    let knapsack = items
+        .into_iter()
+        .powerset()
+        // Stop if the solver should be stopped
+        .take_while(|_| match options.stop_status() {
+            Some(stop_status) => {
+                status = stop_status;
+                false
+            }
+            None => true,
+        })
+        .enumerate()
+        .inspect(|(i, _)| {
+            options.report(Progress::Subsets {
+                done: *i as u64 + 1,
+                total: total_subsets,
+            })
+        })
+        .map(|(_, subset)| subset)
+        // Remove subsets with too much weight
+        .filter(|subset| {
+            subset.iter().map(|&item| item.borrow().weight).sum::<u64>() <= weight_limit
+        })
+        // Get the best knapsack, i.e. the selection with the most profit
+        .max_by_key(|subset| knapsack_profit(subset))
+        // The empty subset always fits into the knapsack, but it may not be tried if stopped immediately
+        .unwrap_or_default();
+    Ok(SolveResult::new(knapsack, status, true))
+}
+
+// ------- Incremental Knapsack ----------------------------------
+
+/// Solves the [maximum knapsack problem](https://en.wikipedia.org/wiki/Knapsack_problem) with
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming) while items are added one by one. The
+/// optimum is always up to date, so there is no need to start over after adding an item.
+///
+/// Adding an item updates the row of maximum profits in `O(weight_limit)`. For reconstructing the chosen items, a bit
+/// per cell remembers whether the item was taken. The changed cells are stored as well, so that the last added item can
+/// be undone. Removing an arbitrary item undoes all items added after it and adds them again.
+///
+/// # Examples
+///
+/// ```
+/// # use aud2::knapsack::{IncrementalKnapsack, Item};
+/// let mut knapsack = IncrementalKnapsack::new(9);
+/// knapsack.add(Item { id: 0, profit: 6, weight: 2 });
+/// knapsack.add(Item { id: 1, profit: 9, weight: 7 });
+/// knapsack.add(Item { id: 2, profit: 8, weight: 6 });
+/// assert_eq!(knapsack.optimum(), 15);
+/// assert_eq!(knapsack.selection().iter().map(|item| item.id).collect::<Vec<_>>(), [0, 1]);
+/// knapsack.remove(1);
+/// assert_eq!(knapsack.optimum(), 14);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalKnapsack {
+    /// The maximum allowed weight of the knapsack.
+    weight_limit: u64,
+    /// The maximum profit for each weight limit from `0` to `weight_limit`, taking all added items into account.
+    row: Vec<u64>,
+    /// One layer per added item in the order the items were added.
+    layers: Vec<IncrementalLayer>,
+}
+
+/// The changes adding an item made to the row of an [IncrementalKnapsack].
+#[derive(Debug, Clone)]
+struct IncrementalLayer {
+    /// The added item.
+    item: Item,
+    /// Bitset over the cells of the row, whether the item was taken for the weight limit of the cell.
+    taken: Vec<u64>,
+    /// The cells changed by adding the item and their old profits.
+    changed_cells: Vec<(usize, u64)>,
+}
+
+impl IncrementalLayer {
+    fn is_taken(&self, index: usize) -> bool {
+        self.taken[index / 64] & (1 << (index % 64)) != 0
+    }
+}
+
+impl IncrementalKnapsack {
+    /// Creates a knapsack without items.
+    pub fn new(weight_limit: u64) -> Self {
+        IncrementalKnapsack {
+            weight_limit,
+            row: vec![0; (weight_limit + 1) as usize],
+            layers: Vec::new(),
+        }
+    }
+
+    /// The maximum allowed weight of the knapsack.
+    pub fn weight_limit(&self) -> u64 {
+        self.weight_limit
+    }
+
+    /// Iterates over all added items in the order they were added.
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.layers.iter().map(|layer| &layer.item)
+    }
+
+    /// The number of added items.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether no items were added.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Adds an item and updates the optimum in `O(weight_limit)`.
+    pub fn add(&mut self, item: Item) {
+        let mut taken = vec![0u64; self.row.len().div_ceil(64)];
+        let mut changed_cells = Vec::new();
+        // Because we override the old row, we go from right ro left.
+        for index in (item.weight.min(self.row.len() as u64) as usize..self.row.len()).rev() {
+            let new_profit = self.row[index - item.weight as usize] + item.profit;
+            if new_profit > self.row[index] {
+                changed_cells.push((index, self.row[index]));
+                self.row[index] = new_profit;
+                taken[index / 64] |= 1 << (index % 64);
+            }
+        }
+        log::debug!(
+            "Added item id={}, changed {} cells, optimum={}",
+            item.id,
+            changed_cells.len(),
+            self.optimum()
+        );
+        self.layers.push(IncrementalLayer {
+            item,
+            taken,
+            changed_cells,
+        });
+    }
+
+    /// Removes the last added item and returns it. Returns `None` if there are no items.
+    pub fn undo(&mut self) -> Option<Item> {
+        let layer = self.layers.pop()?;
+        for (index, old_profit) in layer.changed_cells {
+            self.row[index] = old_profit;
+        }
+        log::debug!(
+            "Removed item id={}, optimum={}",
+            layer.item.id,
+            self.optimum()
+        );
+        Some(layer.item)
+    }
+
+    /// Removes the item with the given `id` and returns it. All items added after it are undone and added again.
+    /// Returns `None` if there is no item with this id.
+    pub fn remove(&mut self, id: usize) -> Option<Item> {
+        let position = self.layers.iter().position(|layer| layer.item.id == id)?;
+        let mut redo_items = Vec::new();
+        while self.layers.len() > position + 1 {
+            redo_items.push(self.undo().expect("There are more layers than position"));
+        }
+        let removed_item = self.undo();
+        for item in redo_items.into_iter().rev() {
+            self.add(item);
+        }
+        removed_item
+    }
+
+    /// The maximum profit reachable with the added items.
+    pub fn optimum(&self) -> u64 {
+        *self.row.last().expect("Row has at least one cell")
+    }
+
+    /// The items in the optimal knapsack. The items are reconstructed in `O(number of items)`.
+    pub fn selection(&self) -> Vec<&Item> {
+        let mut remaining_weight = self.weight_limit as usize;
+        let mut knapsack = Vec::new();
+        for layer in self.layers.iter().rev() {
+            if layer.is_taken(remaining_weight) {
+                knapsack.push(&layer.item);
+                remaining_weight -= layer.item.weight as usize;
+            }
+        }
+        knapsack.reverse();
+        knapsack
+    }
+}
+
+// ------- Verification ----------------------------------
+
+/// A reason why a knapsack solution is not feasible.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Infeasibility {
+    /// The item with this id was chosen more than once.
+    DuplicateId(usize),
+    /// There is no item with this id.
+    UnknownId(usize),
+    /// The chosen items weigh more than the weight limit allows.
+    OverCapacity {
+        /// Total weight of the chosen items.
+        weight: u64,
+        /// The maximum allowed weight of the knapsack.
+        weight_limit: u64,
+    },
+}
+
+impl fmt::Display for Infeasibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Infeasibility::DuplicateId(id) => write!(f, "item id={} was chosen more than once", id),
+            Infeasibility::UnknownId(id) => write!(f, "there is no item with id={}", id),
+            Infeasibility::OverCapacity {
+                weight,
+                weight_limit,
+            } => write!(
+                f,
+                "total weight {} exceeds the weight limit {}",
+                weight, weight_limit
+            ),
+        }
+    }
+}
+
+/// Whether a knapsack solution is optimal.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Optimality {
+    /// The solution is optimal. This is either proven by reaching the upper bound of [fractional_greedy] or by
+    /// comparing with the result of [dynamic_programming].
+    Optimal,
+    /// The solution is not optimal, because [dynamic_programming] found a knapsack with more profit.
+    NotOptimal {
+        /// The optimal profit.
+        optimal_profit: u64,
+    },
+    /// Optimality was neither proven nor refuted.
+    Unknown,
+}
+
+/// The result of verifying a knapsack solution with [verify].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Verification<'a, ItemRef>
+where
+    ItemRef: Borrow<Item>,
+{
+    /// The chosen items. Unknown ids are left out and duplicate ids are only contained once.
+    pub knapsack: Vec<&'a ItemRef>,
+    /// Total profit of the chosen items.
+    pub profit: u64,
+    /// Total weight of the chosen items.
+    pub weight: u64,
+    /// All reasons why the solution is not feasible. The solution is feasible if this is empty.
+    pub infeasibilities: Vec<Infeasibility>,
+    /// Upper bound for the optimal profit, calculated via [fractional_greedy] and rounded down.
+    pub upper_bound: u64,
+    /// Whether the solution is optimal.
+    pub optimality: Optimality,
+}
+
+impl<'a, ItemRef> Verification<'a, ItemRef>
+where
+    ItemRef: Borrow<Item>,
+{
+    /// Whether the solution is feasible, i.e. there are no [Infeasibility]s.
+    pub fn is_feasible(&self) -> bool {
+        self.infeasibilities.is_empty()
+    }
+
+    /// How much profit the solution is at most away from the optimum, i.e. `upper_bound - profit`.
+    pub fn upper_bound_gap(&self) -> u64 {
+        self.upper_bound.saturating_sub(self.profit)
+    }
+}
+
+/// Verifies a knapsack solution, i.e. checks whether it is feasible and how far it is away from the optimum.
+///
+/// The solution is checked for duplicate ids, unknown ids and whether it exceeds the weight limit. If the profit of
+/// the solution reaches the upper bound of [fractional_greedy], the solution is proven to be optimal. Otherwise, if
+/// `prove_optimality` is set, [dynamic_programming] is run to prove or refute optimality.
+///
+/// # Arguments
+///
+/// * `items` - Something that can be turned into an iterator yielding references to [Item]s or something that can be
+///   borrowed as [Item].
+/// * `weight_limit` - The maximum allowed weight of the knapsack.
+/// * `chosen_ids` - The ids of the items in the solution.
+/// * `prove_optimality` - Whether [dynamic_programming] should be run if optimality can not be proven otherwise.
+///
+/// # Returns
+///
+/// The [Verification] of the solution.
+pub fn verify<'a, ItemRef, ItemIter>(
+    items: ItemIter,
+    weight_limit: u64,
+    chosen_ids: &[usize],
+    prove_optimality: bool,
+) -> Verification<'a, ItemRef>
+where
+    ItemRef: Borrow<Item>,
+    ItemIter: IntoIterator<Item = &'a ItemRef>,
+{
+    let items: Vec<&ItemRef> = Vec::from_iter(items);
+    let mut infeasibilities = Vec::new();
+
+    // Look up the chosen items
+    let mut knapsack: Vec<&ItemRef> = Vec::new();
+    for (i, &id) in chosen_ids.iter().enumerate() {
+        if chosen_ids[..i].contains(&id) {
+            // Only report each duplicate id once
+            if chosen_ids[..i].iter().filter(|&&other| other == id).count() == 1 {
+                infeasibilities.push(Infeasibility::DuplicateId(id));
+            }
+            continue;
+        }
+        match items.iter().find(|item| (**item).borrow().id == id) {
+            Some(&item) => knapsack.push(item),
+            None => infeasibilities.push(Infeasibility::UnknownId(id)),
+        }
+    }
+
+    let profit = knapsack_profit(&knapsack);
+    let weight: u64 = knapsack.iter().map(|&item| item.borrow().weight).sum();
+    if weight > weight_limit {
+        infeasibilities.push(Infeasibility::OverCapacity {
+            weight,
+            weight_limit,
+        });
+    }
+
+    let upper_bound = fraction_to_u64(
+        fractional_greedy(items.iter().copied(), weight_limit)
+            .into_iter()
+            .map(|packed_item| packed_item.effective_profit())
+            .sum::<Fraction>(),
+    );
+    log::info!(
+        "profit={} weight={} upper_bound={} infeasibilities={:?}",
+        profit,
+        weight,
+        upper_bound,
+        infeasibilities
+    );
+
+    let optimality = if infeasibilities.is_empty().not() {
+        // An infeasible solution can not be optimal
+        Optimality::Unknown
+    } else if profit >= upper_bound {
+        // No knapsack can have more profit than the upper bound
+        Optimality::Optimal
+    } else if prove_optimality {
+        let optimal_profit =
+            knapsack_profit(&dynamic_programming(items.iter().copied(), weight_limit));
+        if profit >= optimal_profit {
+            Optimality::Optimal
+        } else {
+            Optimality::NotOptimal { optimal_profit }
+        }
+    } else {
+        Optimality::Unknown
+    };
+
+    Verification {
+        knapsack,
+        profit,
+        weight,
+        infeasibilities,
+        upper_bound,
+        optimality,
+    }
+}
+
+// ------- Helper Functions ----------------------------------
+
+/// Serializes `fraction` as string like `"3/4"`, see [PartialPackedItem]. Use it via
+/// `#[serde(serialize_with = "serialize_fraction")]`.
+pub fn serialize_fraction<S: Serializer>(
+    fraction: &Fraction,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(fraction)
+}
+
 /// Calculates the total profit of all items.
 pub fn knapsack_profit<ItemRef>(items: &[&ItemRef]) -> u64
 where
@@ -687,3 +2203,14 @@ pub fn fraction_to_u64(fraction: impl Borrow<Fraction>) -> u64 {
         .parse()
         .expect("Parsing fraction with 0 zero digits after the dot always succeeds")
 }
+
+/// Calculates the binomial coefficient `n choose k`. Returns `None` if it does not fit into an [u64].
+pub(crate) fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    (0..k.min(n - k)).try_fold(1u64, |result, i| {
+        // result * (n - i) / (i + 1) is always an integer, namely (n choose i + 1), which grows with i
+        u64::try_from(result as u128 * (n - i) as u128 / (i + 1) as u128).ok()
+    })
+}
@@ -0,0 +1,248 @@
+//! Export of knapsack instances as [integer linear program](https://en.wikipedia.org/wiki/Integer_programming), so
+//! that they can be solved by an external MILP solver like [GLPK](https://www.gnu.org/software/glpk/) or
+//! [CBC](https://github.com/coin-or/Cbc), and import of the solutions of these solvers.
+//!
+//! Item `id` is modelled by the integer variable `x<id>`, which states how often the item is put into the knapsack.
+
+use std::borrow::Borrow;
+use std::io::{self, Write};
+use std::iter;
+use std::ops::Not;
+
+use super::instance::Instance;
+use super::Item;
+
+/// A knapsack instance as integer linear program. It covers the 0/1 knapsack, the bounded knapsack (items may be
+/// taken multiple times) and the multi-dimensional knapsack (items have multiple weights, each with its own limit).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IlpModel {
+    /// The items that can be put into the knapsack.
+    pub items: Vec<IlpItem>,
+    /// The maximum allowed weight of the knapsack in each dimension.
+    pub weight_limits: Vec<u64>,
+}
+
+/// An item of an [IlpModel].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IlpItem {
+    /// An unique identifier.
+    pub id: usize,
+    /// How much benefit / value one copy of this item provides.
+    pub profit: u64,
+    /// How much weight one copy of this item takes up in each dimension.
+    pub weights: Vec<u64>,
+    /// How often this item may be put into the knapsack. This is 1 for the 0/1 knapsack.
+    pub max_copies: u64,
+}
+
+impl IlpModel {
+    /// Creates the model of a 0/1 knapsack.
+    pub fn from_items<'a, ItemRef, ItemIter>(items: ItemIter, weight_limit: u64) -> Self
+    where
+        ItemRef: 'a + Borrow<Item>,
+        ItemIter: IntoIterator<Item = &'a ItemRef>,
+    {
+        IlpModel {
+            items: items
+                .into_iter()
+                .map(|item| {
+                    let item: &Item = item.borrow();
+                    IlpItem {
+                        id: item.id,
+                        profit: item.profit,
+                        weights: vec![item.weight],
+                        max_copies: 1,
+                    }
+                })
+                .collect(),
+            weight_limits: vec![weight_limit],
+        }
+    }
+
+    /// Returns the 0/1 knapsack described by this model, or `None` if the model has more than one dimension or any item
+    /// may be taken multiple times.
+    pub fn to_instance(&self) -> Option<Instance> {
+        if self.weight_limits.len() != 1 || self.items.iter().any(|item| item.max_copies != 1) {
+            return None;
+        }
+        Some(Instance {
+            weight_limit: self.weight_limits[0],
+            items: self
+                .items
+                .iter()
+                .map(|item| Item {
+                    id: item.id,
+                    profit: item.profit,
+                    weight: item.weights[0],
+                })
+                .collect(),
+        })
+    }
+
+    /// Writes the model in the [CPLEX LP format](https://www.ibm.com/docs/en/icos/22.1.1?topic=cplex-lp-file-format-algebraic-representation).
+    pub fn write_lp(&self, mut writer: impl Write) -> io::Result<()> {
+        self.check_dimensions()?;
+        writeln!(writer, "\\ Knapsack instance exported by aud2")?;
+        writeln!(writer, "Maximize")?;
+        let objective = self
+            .items
+            .iter()
+            .map(|item| format!("{} x{}", item.profit, item.id));
+        write_lp_expression(&mut writer, " obj:", objective)?;
+        writeln!(writer, "Subject To")?;
+        for (dimension, weight_limit) in self.weight_limits.iter().enumerate() {
+            let weights = self
+                .items
+                .iter()
+                .map(|item| format!("{} x{}", item.weights[dimension], item.id));
+            write_lp_expression(&mut writer, &format!(" c{}:", dimension), weights)?;
+            writeln!(writer, "   <= {}", weight_limit)?;
+        }
+        let (binary_items, general_items): (Vec<&IlpItem>, Vec<&IlpItem>) =
+            self.items.iter().partition(|item| item.max_copies == 1);
+        if general_items.is_empty().not() {
+            writeln!(writer, "Bounds")?;
+            for item in &general_items {
+                writeln!(writer, " 0 <= x{} <= {}", item.id, item.max_copies)?;
+            }
+            writeln!(writer, "General")?;
+            for item in &general_items {
+                writeln!(writer, " x{}", item.id)?;
+            }
+        }
+        if binary_items.is_empty().not() {
+            writeln!(writer, "Binary")?;
+            for item in &binary_items {
+                writeln!(writer, " x{}", item.id)?;
+            }
+        }
+        writeln!(writer, "End")
+    }
+
+    /// Writes the model in the [free MPS format](https://lpsolve.sourceforge.net/5.5/mps-format.htm).
+    pub fn write_mps(&self, mut writer: impl Write) -> io::Result<()> {
+        self.check_dimensions()?;
+        writeln!(writer, "NAME knapsack")?;
+        writeln!(writer, "OBJSENSE")?;
+        writeln!(writer, "    MAX")?;
+        writeln!(writer, "ROWS")?;
+        writeln!(writer, " N obj")?;
+        for dimension in 0..self.weight_limits.len() {
+            writeln!(writer, " L c{}", dimension)?;
+        }
+        writeln!(writer, "COLUMNS")?;
+        writeln!(
+            writer,
+            "    MARKER                 'MARKER'                 'INTORG'"
+        )?;
+        for item in &self.items {
+            let entries: Vec<(String, u64)> = iter::once(("obj".to_string(), item.profit))
+                .chain(
+                    item.weights
+                        .iter()
+                        .enumerate()
+                        .map(|(dimension, &weight)| (format!("c{}", dimension), weight)),
+                )
+                .collect();
+            // A record may contain at most two row/value pairs, so the column name is repeated for further pairs
+            for pairs in entries.chunks(2) {
+                write!(writer, " x{}", item.id)?;
+                for (row, value) in pairs {
+                    write!(writer, " {} {}", row, value)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+        writeln!(
+            writer,
+            "    MARKER                 'MARKER'                 'INTEND'"
+        )?;
+        writeln!(writer, "RHS")?;
+        for (dimension, weight_limit) in self.weight_limits.iter().enumerate() {
+            writeln!(writer, " RHS c{} {}", dimension, weight_limit)?;
+        }
+        writeln!(writer, "BOUNDS")?;
+        for item in &self.items {
+            if item.max_copies == 1 {
+                writeln!(writer, " BV BND x{}", item.id)?;
+            } else {
+                writeln!(writer, " UP BND x{} {}", item.id, item.max_copies)?;
+            }
+        }
+        writeln!(writer, "ENDATA")
+    }
+
+    /// Checks that every item has a weight for every dimension.
+    fn check_dimensions(&self) -> io::Result<()> {
+        match self
+            .items
+            .iter()
+            .find(|item| item.weights.len() != self.weight_limits.len())
+        {
+            Some(item) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "item id={} has {} weights, but there are {} weight limits",
+                    item.id,
+                    item.weights.len(),
+                    self.weight_limits.len()
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes the sum of `terms` after `label`, breaking lines after a few terms to keep lines short.
+fn write_lp_expression(
+    writer: &mut impl Write,
+    label: &str,
+    terms: impl Iterator<Item = String>,
+) -> io::Result<()> {
+    write!(writer, "{}", label)?;
+    for (i, term) in terms.enumerate() {
+        if i > 0 && i % 8 == 0 {
+            write!(writer, "\n   ")?;
+        }
+        let operator = if i == 0 { "" } else { "+ " };
+        write!(writer, " {}{}", operator, term)?;
+    }
+    writeln!(writer)
+}
+
+/// Reads the solution file of a MILP solver and returns the ids of the chosen items. An item that is chosen multiple
+/// times is contained multiple times.
+///
+/// The solution file is read line by line. Each line containing a variable `x<id>` followed by its value counts. This
+/// covers the solution files of CBC (`solution <file>`), GLPK (`-o <file>`), SCIP and Gurobi (`.sol`). Variables not
+/// listed in the file are assumed to be 0.
+///
+/// # Examples
+///
+/// ```
+/// # use aud2::knapsack::ilp::read_solution;
+/// let cbc_solution = "Optimal - objective value 15.00000000
+///       0 x0                     1                       6
+///       3 x3                     1                       9
+/// ";
+/// assert_eq!(read_solution(cbc_solution), [0, 3]);
+/// ```
+pub fn read_solution(solution: &str) -> Vec<usize> {
+    let mut ids = Vec::new();
+    for line in solution.lines() {
+        let mut tokens = line.split_whitespace();
+        // Find the variable name and take the next number as its value
+        let id = tokens.find_map(|token| token.strip_prefix('x')?.parse::<usize>().ok());
+        let id = match id {
+            Some(id) => id,
+            None => continue,
+        };
+        let value = tokens.find_map(|token| token.parse::<f64>().ok());
+        if let Some(value) = value {
+            let copies = value.round().max(0.0) as usize;
+            log::debug!("x{}={} -> {} copies", id, value, copies);
+            ids.extend(std::iter::repeat_n(id, copies));
+        }
+    }
+    ids
+}
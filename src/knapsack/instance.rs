@@ -0,0 +1,356 @@
+//! Readers for knapsack instances in common benchmark formats:
+//!
+//! - The instance files of [David Pisinger](http://hjemmesider.diku.dk/~pisinger/codes.html), e.g. the "hard"
+//!   instances `knapPI_<class>_<n>_<range>.csv`.
+//! - The `mknap` format of the [OR-Library](http://people.brunel.ac.uk/~mastjjb/jeb/orlib/mknapinfo.html), which
+//!   describes multi-dimensional knapsacks.
+//! - JSON and TOML files that contain the weight limit and the items, see [Instance]. Items may be bounded, see
+//!   [parse_json_model].
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use super::ilp::{IlpItem, IlpModel};
+use super::Item;
+
+/// A 0/1 knapsack instance, i.e. the items and the maximum allowed weight of the knapsack.
+///
+/// This is also the layout of JSON and TOML instances:
+///
+/// ```
+/// # use aud2::knapsack::instance::{parse_json, Instance};
+/// # use aud2::knapsack::Item;
+/// let json = r#"{
+///     "weight_limit": 5,
+///     "items": [
+///         {"id": 1, "profit": 6, "weight": 2},
+///         {"id": 2, "profit": 5, "weight": 3}
+///     ]
+/// }"#;
+/// let instance = parse_json(json).unwrap();
+/// assert_eq!(instance.weight_limit, 5);
+/// assert_eq!(instance.items[1], Item { id: 2, profit: 5, weight: 3 });
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Instance {
+    /// The maximum allowed weight of the knapsack.
+    pub weight_limit: u64,
+    /// The items that can be put into the knapsack.
+    pub items: Vec<Item>,
+}
+
+/// An error while parsing an instance file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// The line (starting at 1) the error occurred in, if known.
+    pub line: Option<usize>,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses all instances of a file in Pisinger's format. Each instance consists of a name, some header lines like
+/// `n <item count>`, `c <weight limit>` and `z <optimal profit>`, and one line `id,profit,weight,x` per item, where `x`
+/// marks the items of an optimal solution. Instances are separated by a line of dashes:
+///
+/// ```text
+/// knapPI_1_50_1000_1
+/// n 50
+/// c 995
+/// z 8373
+/// time 0.00
+/// 1,94,485,0
+/// 2,506,326,0
+/// ...
+/// -----
+/// ```
+///
+/// Only `c` and the items are required, everything else is ignored apart from checking the item count `n`.
+pub fn parse_pisinger(text: &str) -> Result<Vec<Instance>, ParseError> {
+    let mut instances = Vec::new();
+    let mut weight_limit = None;
+    let mut item_count: Option<usize> = None;
+    let mut items = Vec::new();
+    let mut start_line = 1;
+    // An additional separator at the end finishes the last instance
+    let lines = text.lines().map(str::trim).chain(["-----"]);
+    for (index, line) in lines.enumerate() {
+        let line_number = index + 1;
+        if line.starts_with("---") {
+            if weight_limit.is_none() && items.is_empty() {
+                // Nothing but the name or blank lines, e.g. after the last separator of the file
+                item_count = None;
+                start_line = line_number + 1;
+                continue;
+            }
+            let weight_limit = weight_limit
+                .take()
+                .ok_or_else(|| ParseError::new(start_line, "instance without capacity line `c`"))?;
+            if let Some(item_count) = item_count.take() {
+                if item_count != items.len() {
+                    return Err(ParseError::new(
+                        start_line,
+                        format!("expected {} items, but got {}", item_count, items.len()),
+                    ));
+                }
+            }
+            instances.push(Instance {
+                weight_limit,
+                items: std::mem::take(&mut items),
+            });
+            start_line = line_number + 1;
+        } else if line.contains(',') {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() < 3 {
+                return Err(ParseError::new(
+                    line_number,
+                    "expected an item line `id,profit,weight`",
+                ));
+            }
+            items.push(Item {
+                id: parse_number(fields[0], line_number)?,
+                profit: parse_number(fields[1], line_number)?,
+                weight: parse_number(fields[2], line_number)?,
+            });
+        } else {
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                ["n", value] => item_count = Some(parse_number(value, line_number)?),
+                ["c", value] => weight_limit = Some(parse_number(value, line_number)?),
+                // Instance name, optimal profit, runtime and blank lines
+                _ => {}
+            }
+        }
+    }
+    if instances.is_empty() {
+        return Err(ParseError {
+            line: None,
+            message: "no instance found".to_string(),
+        });
+    }
+    Ok(instances)
+}
+
+/// Parses all instances of a file in the OR-Library `mknap` format. The file is a whitespace separated list of
+/// numbers: The number of instances, followed by each instance as
+///
+/// - the number of items `n`, the number of dimensions `m` and the optimal profit (0 if unknown),
+/// - the profits of the `n` items,
+/// - for each of the `m` dimensions the weights of the `n` items,
+/// - the `m` weight limits.
+///
+/// The items get the id's `1..=n`.
+pub fn parse_or_library(text: &str) -> Result<Vec<IlpModel>, ParseError> {
+    let mut tokens = text
+        .lines()
+        .enumerate()
+        .flat_map(|(index, line)| line.split_whitespace().map(move |token| (index + 1, token)));
+    let mut last_line = 1;
+    let mut next_number = |what: &str| -> Result<u64, ParseError> {
+        let (line, token) = tokens.next().ok_or_else(|| ParseError {
+            line: Some(last_line),
+            message: format!("unexpected end of file, expected {}", what),
+        })?;
+        last_line = line;
+        // Some files write integral numbers as decimals, e.g. `0.000000` as unknown optimum
+        match token.parse::<u64>() {
+            Ok(number) => Ok(number),
+            Err(_) => match token.parse::<f64>() {
+                Ok(number) if number >= 0.0 && number.fract() == 0.0 => Ok(number as u64),
+                _ => Err(ParseError::new(
+                    line,
+                    format!("expected {}, but got {:?}", what, token),
+                )),
+            },
+        }
+    };
+
+    let instance_count = next_number("the number of instances")?;
+    let mut models = Vec::new();
+    for _ in 0..instance_count {
+        let item_count = next_number("the number of items")? as usize;
+        let dimensions = next_number("the number of dimensions")? as usize;
+        next_number("the optimal profit")?;
+        let profits = (0..item_count)
+            .map(|_| next_number("a profit"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let weights = (0..dimensions)
+            .map(|_| {
+                (0..item_count)
+                    .map(|_| next_number("a weight"))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let weight_limits = (0..dimensions)
+            .map(|_| next_number("a weight limit"))
+            .collect::<Result<Vec<_>, _>>()?;
+        models.push(IlpModel {
+            items: profits
+                .into_iter()
+                .enumerate()
+                .map(|(index, profit)| IlpItem {
+                    id: index + 1,
+                    profit,
+                    weights: weights.iter().map(|weights| weights[index]).collect(),
+                    max_copies: 1,
+                })
+                .collect(),
+            weight_limits,
+        });
+    }
+    if models.is_empty() {
+        return Err(ParseError {
+            line: None,
+            message: "no instance found".to_string(),
+        });
+    }
+    Ok(models)
+}
+
+/// Parses an [Instance] from JSON. Fails if an item may be taken multiple times, see [parse_json_model].
+pub fn parse_json(text: &str) -> Result<Instance, ParseError> {
+    parse_json_model(text)?
+        .to_instance()
+        .ok_or_else(bounded_items_error)
+}
+
+/// Parses a bounded knapsack from JSON. The layout is the one of [Instance], but each item may have a field
+/// `max_copies`, which states how often it may be put into the knapsack (default 1):
+///
+/// ```
+/// # use aud2::knapsack::instance::parse_json_model;
+/// let json = r#"{
+///     "weight_limit": 5,
+///     "items": [
+///         {"id": 1, "profit": 6, "weight": 2, "max_copies": 3},
+///         {"id": 2, "profit": 5, "weight": 3}
+///     ]
+/// }"#;
+/// let model = parse_json_model(json).unwrap();
+/// assert_eq!(model.items[0].max_copies, 3);
+/// assert_eq!(model.items[1].max_copies, 1);
+/// ```
+pub fn parse_json_model(text: &str) -> Result<IlpModel, ParseError> {
+    serde_json::from_str::<BoundedInstance>(text)
+        .map(IlpModel::from)
+        .map_err(|err| ParseError {
+            line: (err.line() > 0).then_some(err.line()),
+            message: err.to_string(),
+        })
+}
+
+/// Parses an [Instance] from TOML. Fails if an item may be taken multiple times, see [parse_toml_model].
+///
+/// ```toml
+/// weight_limit = 5
+///
+/// [[items]]
+/// id = 1
+/// profit = 6
+/// weight = 2
+/// ```
+pub fn parse_toml(text: &str) -> Result<Instance, ParseError> {
+    parse_toml_model(text)?
+        .to_instance()
+        .ok_or_else(bounded_items_error)
+}
+
+/// Parses a bounded knapsack from TOML. Like in [parse_json_model], each item may have a field `max_copies`.
+pub fn parse_toml_model(text: &str) -> Result<IlpModel, ParseError> {
+    toml::from_str::<BoundedInstance>(text)
+        .map(IlpModel::from)
+        .map_err(|err| ParseError {
+            line: None,
+            message: err.message().to_string(),
+        })
+}
+
+/// The layout of JSON and TOML instances, whose items may be taken multiple times.
+#[derive(Deserialize)]
+struct BoundedInstance {
+    weight_limit: u64,
+    items: Vec<BoundedItem>,
+}
+
+/// An item of a [BoundedInstance].
+#[derive(Deserialize)]
+struct BoundedItem {
+    id: usize,
+    profit: u64,
+    weight: u64,
+    #[serde(default = "one")]
+    max_copies: u64,
+}
+
+impl From<BoundedInstance> for IlpModel {
+    fn from(instance: BoundedInstance) -> Self {
+        IlpModel {
+            items: instance
+                .items
+                .into_iter()
+                .map(|item| IlpItem {
+                    id: item.id,
+                    profit: item.profit,
+                    weights: vec![item.weight],
+                    max_copies: item.max_copies,
+                })
+                .collect(),
+            weight_limits: vec![instance.weight_limit],
+        }
+    }
+}
+
+/// Default of [BoundedItem::max_copies].
+fn one() -> u64 {
+    1
+}
+
+/// The error of [parse_json] and [parse_toml] for instances that are not 0/1 knapsacks.
+fn bounded_items_error() -> ParseError {
+    ParseError {
+        line: None,
+        message: "items with max_copies other than 1 are only supported by a bounded knapsack"
+            .to_string(),
+    }
+}
+
+/// Returns whether `text` looks like a file in Pisinger's format, i.e. whether it contains a capacity line `c <number>`.
+pub fn is_pisinger(text: &str) -> bool {
+    text.lines()
+        .any(|line| matches!(line.split_whitespace().collect::<Vec<_>>()[..], ["c", value] if value.parse::<u64>().is_ok()))
+}
+
+/// Returns whether `text` looks like a file in the OR-Library format, i.e. whether it consists of numbers only.
+pub fn is_or_library(text: &str) -> bool {
+    let mut tokens = text.split_whitespace().peekable();
+    tokens.peek().is_some() && tokens.all(|token| token.parse::<f64>().is_ok())
+}
+
+/// Parses `value` in line `line_number` as number.
+fn parse_number<T: std::str::FromStr>(value: &str, line_number: usize) -> Result<T, ParseError> {
+    value.parse().map_err(|_| {
+        ParseError::new(
+            line_number,
+            format!("expected a non-negative integer, but got {:?}", value),
+        )
+    })
+}
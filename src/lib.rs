@@ -1,4 +1,11 @@
 //! Algorithms taught at my university in the course "Algorithms and Data Structures 2" implemented in Rust.
 
+pub mod compare;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod generator;
 pub mod knapsack;
+pub mod partition;
+#[cfg(feature = "python")]
+mod python;
 pub mod subset_sum;
@@ -1,11 +1,36 @@
 mod cli;
+mod output;
 
-use crate::cli::{CliArgs, CliCommands, KnapsackFractionalGreedy};
+use crate::cli::{
+    CliArgs, CliCommands, IlpFormat, InstanceFormat, KnapsackFractionalGreedy, OutputFormat,
+};
+use crate::output::{
+    print_output, ClosestSubsetResult, ComparisonRow, ComparisonRun, ComparisonTable,
+    FractionalSolution, ImportedSolution, Output, PartitionResult, SolveOutput, SubsetSumCount,
+    SubsetSumResult, VerificationReport,
+};
 use anyhow::Context;
-use aud2::knapsack::{Item, PartialPackedItem};
-use aud2::subset_sum::{subset_sum_set, subset_sum_vec};
-use fraction::Fraction;
+use aud2::generator::InstanceClass;
+use aud2::knapsack::ilp::IlpModel;
+use aud2::knapsack::instance::{
+    is_or_library, is_pisinger, parse_json_model, parse_or_library, parse_pisinger,
+    parse_toml_model, Instance,
+};
+use aud2::knapsack::{Item, SolveOptions, SolveResult, SolveStatus};
+use aud2::subset_sum::{
+    approximate_subset_sum, closest_subset_sum_set, closest_subset_sum_vec, nearest_subset_sum_set,
+    nearest_subset_sum_vec, subset_sum_bitset, subset_sum_bitset_witness, subset_sum_cardinality,
+    subset_sum_count, subset_sum_set_witness, subset_sum_signed_set, subset_sum_signed_vec,
+    subset_sum_vec_witness, Subset,
+};
+use std::ffi::OsStr;
 use std::fs;
+use std::io;
+use std::num::NonZeroU128;
+use std::ops::Not;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 fn main() -> anyhow::Result<()> {
     init_logger();
@@ -18,20 +43,48 @@ fn main() -> anyhow::Result<()> {
 /// Inspects the passed command line arguments and starts the corresponding cli wrapper function for the selected
 /// subcommand.
 fn invoke_subcommand(cli_args: CliArgs) -> anyhow::Result<()> {
+    let output = cli_args.output;
     match cli_args.subcommand {
         CliCommands::KnapsackFractionalGreedy(sub_cli_args) => {
-            knapsack_fractional_greedy_cli(sub_cli_args)
+            knapsack_fractional_greedy_cli(sub_cli_args, output)
         }
         CliCommands::KnapsackIntegerGreedy(sub_cli_args) => {
-            knapsack_integer_greedy_cli(sub_cli_args)
+            knapsack_integer_greedy_cli(sub_cli_args, output)
+        }
+        CliCommands::KnapsackModifiedGreedy(sub_cli_args) => {
+            knapsack_modified_greedy_cli(sub_cli_args, output)
         }
         CliCommands::KnapsackDynamicProgramming(sub_cli_args) => {
-            knapsack_dynamic_programming_cli(sub_cli_args)
+            knapsack_dynamic_programming_cli(sub_cli_args, output)
+        }
+        CliCommands::KnapsackGreedyK(sub_cli_args) => knapsack_greedy_k_cli(sub_cli_args, output),
+        CliCommands::KnapsackBranchBound(sub_cli_args) => {
+            knapsack_branch_and_bound(sub_cli_args, output)
+        }
+        CliCommands::KnapsackMeetInTheMiddle(sub_cli_args) => {
+            knapsack_meet_in_the_middle_cli(sub_cli_args, output)
+        }
+        CliCommands::GenerateKnapsack(sub_cli_args) => generate_knapsack_cli(sub_cli_args, output),
+        CliCommands::KnapsackCompare(sub_cli_args) => knapsack_compare_cli(sub_cli_args, output),
+        CliCommands::KnapsackVerify(sub_cli_args) => knapsack_verify_cli(sub_cli_args, output),
+        CliCommands::KnapsackExport(sub_cli_args) => knapsack_export_cli(sub_cli_args),
+        CliCommands::KnapsackImportSolution(sub_cli_args) => {
+            knapsack_import_solution_cli(sub_cli_args, output)
+        }
+        CliCommands::SubsetSumRowSumSet(sub_cli_args) => {
+            subset_sum_row_set_cli(sub_cli_args, output)
+        }
+        CliCommands::SubsetSumFullTable(sub_cli_args) => {
+            subset_sum_full_table_cli(sub_cli_args, output)
+        }
+        CliCommands::SubsetSumBitset(sub_cli_args) => subset_sum_bitset_cli(sub_cli_args, output),
+        CliCommands::SubsetSumApproximate(sub_cli_args) => {
+            subset_sum_approximate_cli(sub_cli_args, output)
+        }
+        CliCommands::NumberPartition(sub_cli_args) => partition_cli(sub_cli_args, output),
+        CliCommands::NumberPartitionKarmarkarKarp(sub_cli_args) => {
+            partition_karmarkar_karp_cli(sub_cli_args, output)
         }
-        CliCommands::KnapsackGreedyK(sub_cli_args) => knapsack_greedy_k_cli(sub_cli_args),
-        CliCommands::KnapsackBranchBound(sub_cli_args) => knapsack_branch_and_bound(sub_cli_args),
-        CliCommands::SubsetSumRowSumSet(sub_cli_args) => subset_sum_row_set_cli(sub_cli_args),
-        CliCommands::SubsetSumFullTable(sub_cli_args) => subset_sum_full_table_cli(sub_cli_args),
     }
 }
 
@@ -39,165 +92,704 @@ fn invoke_subcommand(cli_args: CliArgs) -> anyhow::Result<()> {
 // call a library function and print its result.
 
 /// CLI wrapper for [aud2::knapsack::fractional_greedy].
-fn knapsack_fractional_greedy_cli(cli_args: cli::KnapsackFractionalGreedy) -> anyhow::Result<()> {
+fn knapsack_fractional_greedy_cli(
+    cli_args: cli::KnapsackFractionalGreedy,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let KnapsackFractionalGreedy {
         items_csv,
-        weight_limit: weight_capacity,
+        format,
+        weight_limit,
         flipped_csv,
     } = cli_args;
-    let items: Vec<Item> = read_csv(&items_csv, flipped_csv).context("Read items")?;
-
-    let chosen_items = aud2::knapsack::fractional_greedy(&items, weight_capacity);
-    println!("Knapsack:");
-    for chosen_item in &chosen_items {
-        println!(
-            "id={:<2} take_ratio={:<3}",
-            chosen_item.item.id, chosen_item.take_ratio
-        );
-    }
-    let total_profit: Fraction = chosen_items
-        .iter()
-        .map(PartialPackedItem::effective_profit)
-        .sum();
-    println!(
-        "total_profit={} = approx. {:.3}",
-        total_profit, total_profit
-    );
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
 
-    Ok(())
+    let chosen_items = aud2::knapsack::fractional_greedy(&items, weight_limit);
+    print_output(output, &FractionalSolution::new(chosen_items))
 }
 
-/// CLI wrapper for [subset_sum_row_sum_set].
-fn subset_sum_row_set_cli(cli_args: cli::SubsetSumRowSet) -> anyhow::Result<()> {
+/// CLI wrapper for [subset_sum_set_witness] and [subset_sum_signed_set] for negative numbers.
+fn subset_sum_row_set_cli(
+    cli_args: cli::SubsetSumRowSet,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let cli::SubsetSumRowSet {
         numbers,
+        numbers_file,
         sum: limit,
+        count,
+        modulus,
+        at_most,
+        nearest,
+        min_count,
+        max_count,
     } = cli_args;
-    println!("Input numbers: {:?}", numbers);
-    let reachable = subset_sum_set(&numbers, limit);
-    println!("Sum {} reachable: {}", limit, reachable);
-    Ok(())
+    let (numbers, labels) = read_numbers(numbers, numbers_file)?;
+    if modulus.is_some() && count.not() {
+        anyhow::bail!("--modulus requires --count");
+    }
+    let limit_count = min_count.is_some() || max_count.is_some();
+    check_exclusive_flags(count, at_most, nearest, limit_count)?;
+    if limit_count {
+        return subset_sum_cardinality_cli(&numbers, labels, limit, min_count, max_count, output);
+    }
+    if count {
+        return subset_sum_count_cli(&numbers, limit, modulus, output);
+    }
+    if at_most {
+        return subset_sum_closest_cli(&numbers, labels, limit, closest_subset_sum_set, output);
+    }
+    if nearest {
+        return subset_sum_closest_cli(&numbers, labels, limit, nearest_subset_sum_set, output);
+    }
+    // The unsigned algorithm only needs sums up to limit
+    let witness = match to_unsigned(&numbers, limit) {
+        Some((unsigned_numbers, unsigned_limit)) => {
+            subset_sum_set_witness(&unsigned_numbers, unsigned_limit)
+        }
+        None => subset_sum_signed_set(&numbers, limit),
+    };
+    print_output(
+        output,
+        &SubsetSumResult::new(numbers, limit, witness, labels),
+    )
 }
 
-/// CLI wrapper for [subset_sum_full_bool_table].
-fn subset_sum_full_table_cli(cli_args: cli::SubsetSumFullTable) -> anyhow::Result<()> {
+/// CLI wrapper for [subset_sum_vec_witness] and [subset_sum_signed_vec] for negative numbers.
+fn subset_sum_full_table_cli(
+    cli_args: cli::SubsetSumFullTable,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let cli::SubsetSumFullTable {
         numbers,
+        numbers_file,
         sum: limit,
+        count,
+        modulus,
+        at_most,
+        nearest,
+        min_count,
+        max_count,
     } = cli_args;
-    println!("Input numbers: {:?}", numbers);
-    let reachable = subset_sum_vec(&numbers, limit);
-    println!("Sum {} reachable: {}", limit, reachable);
-    Ok(())
+    let (numbers, labels) = read_numbers(numbers, numbers_file)?;
+    if modulus.is_some() && count.not() {
+        anyhow::bail!("--modulus requires --count");
+    }
+    let limit_count = min_count.is_some() || max_count.is_some();
+    check_exclusive_flags(count, at_most, nearest, limit_count)?;
+    if limit_count {
+        return subset_sum_cardinality_cli(&numbers, labels, limit, min_count, max_count, output);
+    }
+    if count {
+        return subset_sum_count_cli(&numbers, limit, modulus, output);
+    }
+    if at_most {
+        return subset_sum_closest_cli(&numbers, labels, limit, closest_subset_sum_vec, output);
+    }
+    if nearest {
+        return subset_sum_closest_cli(&numbers, labels, limit, nearest_subset_sum_vec, output);
+    }
+    // The unsigned algorithm only needs sums up to limit
+    let witness = match to_unsigned(&numbers, limit) {
+        Some((unsigned_numbers, unsigned_limit)) => {
+            subset_sum_vec_witness(&unsigned_numbers, unsigned_limit)
+        }
+        None => subset_sum_signed_vec(&numbers, limit),
+    };
+    print_output(
+        output,
+        &SubsetSumResult::new(numbers, limit, witness, labels),
+    )
+}
+
+/// CLI wrapper for [subset_sum_bitset] and [subset_sum_bitset_witness] with `--witness`.
+fn subset_sum_bitset_cli(
+    cli_args: cli::SubsetSumBitset,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::SubsetSumBitset {
+        numbers,
+        numbers_file,
+        sum: limit,
+        witness,
+    } = cli_args;
+    let (numbers, labels) = read_numbers(numbers, numbers_file)?;
+    let (numbers, limit) = to_unsigned(&numbers, limit)
+        .context("subsum-bitset only supports non-negative numbers and sums")?;
+    let result = if witness {
+        let witness = subset_sum_bitset_witness(&numbers, limit);
+        SubsetSumResult::new(numbers, limit, witness, labels)
+    } else {
+        let reachable = subset_sum_bitset(&numbers, limit);
+        SubsetSumResult::without_witness(numbers, limit, reachable)
+    };
+    print_output(output, &result)
+}
+
+/// CLI wrapper for [approximate_subset_sum].
+fn subset_sum_approximate_cli(
+    cli_args: cli::SubsetSumApproximate,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::SubsetSumApproximate {
+        sum: target,
+        epsilon,
+        numbers,
+        numbers_file,
+    } = cli_args;
+    let (numbers, labels) = read_numbers(numbers, numbers_file)?;
+    if epsilon <= 0.0 || epsilon.is_nan() {
+        anyhow::bail!("--epsilon must be positive");
+    }
+    let subset = approximate_subset_sum(&numbers, target, epsilon);
+    print_output(
+        output,
+        &ClosestSubsetResult::new(numbers, target, subset, labels),
+    )
+}
+
+/// CLI wrapper for the closest subset sum `solver`, like [closest_subset_sum_set] or [nearest_subset_sum_set], used by
+/// the subset sum subcommands with `--at-most` or `--nearest`.
+fn subset_sum_closest_cli(
+    numbers: &[i64],
+    labels: Option<Vec<String>>,
+    target: i64,
+    solver: fn(&[u64], u64) -> Subset,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let (numbers, target) = to_unsigned(numbers, target)
+        .context("--at-most and --nearest only support non-negative numbers and sums")?;
+    let subset = solver(&numbers, target);
+    print_output(
+        output,
+        &ClosestSubsetResult::new(numbers, target, subset, labels),
+    )
+}
+
+/// CLI wrapper for [subset_sum_cardinality], used by the subset sum subcommands with `--min-count` or `--max-count`.
+fn subset_sum_cardinality_cli(
+    numbers: &[i64],
+    labels: Option<Vec<String>>,
+    limit: i64,
+    min_count: Option<usize>,
+    max_count: Option<usize>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let (numbers, limit) = to_unsigned(numbers, limit)
+        .context("--min-count and --max-count only support non-negative numbers and sums")?;
+    let counts = min_count.unwrap_or(0)..=max_count.unwrap_or(numbers.len());
+    let witness = subset_sum_cardinality(&numbers, limit, counts);
+    print_output(
+        output,
+        &SubsetSumResult::new(numbers, limit, witness, labels),
+    )
+}
+
+/// CLI wrapper for [subset_sum_count], used by the subset sum subcommands with `--count`.
+fn subset_sum_count_cli(
+    numbers: &[i64],
+    limit: i64,
+    modulus: Option<NonZeroU128>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let (numbers, limit) = to_unsigned(numbers, limit)
+        .context("--count only supports non-negative numbers and sums")?;
+    let counts = subset_sum_count(&numbers, limit, modulus)?;
+    print_output(
+        output,
+        &SubsetSumCount {
+            numbers,
+            sum: limit,
+            count: counts[limit as usize],
+            modulus: modulus.map(NonZeroU128::get),
+        },
+    )
+}
+
+/// CLI wrapper for [aud2::partition::balanced_partition].
+fn partition_cli(cli_args: cli::NumberPartition, output: OutputFormat) -> anyhow::Result<()> {
+    let cli::NumberPartition {
+        numbers_file,
+        numbers,
+    } = cli_args;
+    let (numbers, labels) = read_numbers(numbers, numbers_file)?;
+    let partition = aud2::partition::balanced_partition(&numbers)?;
+    print_output(
+        output,
+        &PartitionResult::new(numbers, partition, Some(true), labels),
+    )
+}
+
+/// CLI wrapper for [aud2::partition::karmarkar_karp] and [aud2::partition::complete_karmarkar_karp].
+fn partition_karmarkar_karp_cli(
+    cli_args: cli::NumberPartitionKarmarkarKarp,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::NumberPartitionKarmarkarKarp {
+        parts,
+        complete,
+        time_limit,
+        numbers_file,
+        numbers,
+    } = cli_args;
+    let (numbers, labels) = read_numbers(numbers, numbers_file)?;
+    if parts == 0 {
+        anyhow::bail!("--parts must be at least 1");
+    }
+    if complete.not() {
+        let partition = aud2::partition::karmarkar_karp(&numbers, parts)?;
+        return print_output(
+            output,
+            &PartitionResult::new(numbers, partition, None, labels),
+        );
+    }
+    if parts > aud2::partition::MAX_COMPLETE_PARTS {
+        anyhow::bail!(
+            "--complete supports at most {} parts",
+            aud2::partition::MAX_COMPLETE_PARTS
+        );
+    }
+
+    let options = solve_options(time_limit)?;
+    let result = aud2::partition::complete_karmarkar_karp(&numbers, parts, &options)?;
+    if result.status != SolveStatus::Completed {
+        eprintln!("Note: Search stopped early ({:?})", result.status);
+    }
+    // The partition may still be optimal if stopped early, but this is not proven
+    let optimal = (result.status == SolveStatus::Completed).then_some(true);
+    print_output(
+        output,
+        &PartitionResult::new(numbers, result.partition, optimal, labels),
+    )
 }
 
-/// CLI wrapper for [aud2::knapsack::dynamic_programming].
+/// Creates the [SolveOptions] that stop a solver after `time_limit` seconds, if given.
+fn solve_options(time_limit: Option<f64>) -> anyhow::Result<SolveOptions<'static>> {
+    let deadline = time_limit
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds)
+                .map(|time_limit| Instant::now() + time_limit)
+                .context("Invalid --time-limit")
+        })
+        .transpose()?;
+    Ok(SolveOptions {
+        deadline,
+        ..SolveOptions::default()
+    })
+}
+
+/// Prints the knapsack of `result` with its status, i.e. whether the solver was stopped early.
+fn print_solve_result(
+    output: OutputFormat,
+    result: SolveResult<Item>,
+    weight_limit: u64,
+) -> anyhow::Result<()> {
+    print_output(output, &SolveOutput::new(result, weight_limit))
+}
+
+/// CLI wrapper for [aud2::knapsack::dynamic_programming_with_memory_budget].
 fn knapsack_dynamic_programming_cli(
     cli_args: cli::KnapsackDynamicProgramming,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     let cli::KnapsackDynamicProgramming {
         items_csv,
+        format,
         flipped_csv,
         weight_limit,
+        time_limit,
+        memory_budget_mib,
     } = cli_args;
-    let items: Vec<Item> = read_csv(&items_csv, flipped_csv).context("Read items")?;
-    let knapsack = aud2::knapsack::dynamic_programming(&items, weight_limit);
-    println!("Knapsack:");
-    println!(
-        "id's: {:?}",
-        knapsack.iter().map(|item| item.id).collect::<Vec<_>>()
-    );
-    println!(
-        "Total profit: {}",
-        knapsack.iter().map(|item| item.profit).sum::<u64>()
-    );
-    println!(
-        "Total weight {} of allowed weight limit {}",
-        knapsack.iter().map(|item| item.weight).sum::<u64>(),
-        weight_limit
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let options = solve_options(time_limit)?;
+    // Without a budget, the estimate never exceeds it, so the dynamic programming table is always used
+    let memory_budget = memory_budget_mib.map_or(u64::MAX, |memory_budget_mib| {
+        memory_budget_mib.saturating_mul(1024 * 1024)
+    });
+    let result = aud2::knapsack::dynamic_programming_with_memory_budget_with_options(
+        &items,
+        weight_limit,
+        memory_budget,
+        &options,
     );
-    Ok(())
+    print_solve_result(output, result, weight_limit)
 }
 
 /// CLI wrapper for [aud2::knapsack::integer_greedy].
-fn knapsack_integer_greedy_cli(cli_args: cli::KnapsackIntegerGreedy) -> anyhow::Result<()> {
+fn knapsack_integer_greedy_cli(
+    cli_args: cli::KnapsackIntegerGreedy,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let cli::KnapsackIntegerGreedy {
         items_csv,
+        format,
         weight_limit,
+        time_limit,
         flipped_csv,
+        strategy,
     } = cli_args;
-    let items: Vec<Item> = read_csv(&items_csv, flipped_csv).context("Read items")?;
-    let knapsack = aud2::knapsack::integer_greedy(&items, weight_limit);
-    println!("Knapsack:");
-    println!(
-        "id's: {:?}",
-        knapsack.iter().map(|item| item.id).collect::<Vec<_>>()
-    );
-    println!(
-        "Total profit: {}",
-        knapsack.iter().map(|item| item.profit).sum::<u64>()
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let result = aud2::knapsack::integer_greedy_with_options(
+        &items,
+        weight_limit,
+        strategy,
+        &solve_options(time_limit)?,
     );
-    println!(
-        "Total weight {} of allowed weight limit {}",
-        knapsack.iter().map(|item| item.weight).sum::<u64>(),
-        weight_limit
+    print_solve_result(output, result, weight_limit)
+}
+
+/// CLI wrapper for [aud2::knapsack::modified_greedy].
+fn knapsack_modified_greedy_cli(
+    cli_args: cli::KnapsackModifiedGreedy,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::KnapsackModifiedGreedy {
+        items_csv,
+        format,
+        flipped_csv,
+        weight_limit,
+        time_limit,
+    } = cli_args;
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let result = aud2::knapsack::modified_greedy_with_options(
+        &items,
+        weight_limit,
+        &solve_options(time_limit)?,
     );
-    Ok(())
+    print_solve_result(output, result, weight_limit)
 }
 
 /// CLI wrapper for [aud2::knapsack::greedy_k].
-fn knapsack_greedy_k_cli(cli_args: cli::KnapsackGreedyK) -> anyhow::Result<()> {
+fn knapsack_greedy_k_cli(
+    cli_args: cli::KnapsackGreedyK,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let cli::KnapsackGreedyK {
         items_csv,
+        format,
         flipped_csv,
         weight_limit,
+        time_limit,
         k,
     } = cli_args;
-    let items: Vec<Item> = read_csv(&items_csv, flipped_csv).context("Read items")?;
-    let knapsack = aud2::knapsack::greedy_k(&items, weight_limit, k);
-    println!("Knapsack:");
-    println!(
-        "id's: {:?}",
-        knapsack.iter().map(|item| item.id).collect::<Vec<_>>()
-    );
-    println!(
-        "Total profit: {}",
-        knapsack.iter().map(|item| item.profit).sum::<u64>()
-    );
-    println!(
-        "Total weight {} of allowed weight limit {}",
-        knapsack.iter().map(|item| item.weight).sum::<u64>(),
-        weight_limit
-    );
-    Ok(())
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let result =
+        aud2::knapsack::greedy_k_with_options(&items, weight_limit, k, &solve_options(time_limit)?);
+    print_solve_result(output, result, weight_limit)
 }
 
 /// CLI wrapper for [aud2::knapsack::branch_and_bound].
-fn knapsack_branch_and_bound(cli_args: cli::KnapsackBranchBound) -> anyhow::Result<()> {
+fn knapsack_branch_and_bound(
+    cli_args: cli::KnapsackBranchBound,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let cli::KnapsackBranchBound {
         items_csv,
+        format,
         flipped_csv,
         weight_limit,
+        time_limit,
+        initial_solution,
+        compare_cold,
     } = cli_args;
-    let items: Vec<Item> = read_csv(&items_csv, flipped_csv).context("Read items")?;
-    let knapsack = aud2::knapsack::branch_and_bound(&items, weight_limit);
-    println!("Knapsack:");
-    println!(
-        "id's: {:?}",
-        knapsack.iter().map(|item| item.id).collect::<Vec<_>>()
-    );
-    println!(
-        "Total profit: {}",
-        knapsack.iter().map(|item| item.profit).sum::<u64>()
-    );
-    println!(
-        "Total weight {} of allowed weight limit {}",
-        knapsack.iter().map(|item| item.weight).sum::<u64>(),
-        weight_limit
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    if compare_cold && initial_solution.is_none() {
+        anyhow::bail!("--compare-cold requires an --initial-solution");
+    }
+    let result = match initial_solution {
+        Some(initial_solution) => {
+            let (warm_result, warm_statistics) = aud2::knapsack::branch_and_bound_with_incumbent(
+                &items,
+                weight_limit,
+                &initial_solution,
+                &solve_options(time_limit)?,
+            )
+            .context("Initial solution")?;
+            if compare_cold {
+                // The cold start gets the same time limit as the warm start
+                let (_, statistics) = aud2::knapsack::branch_and_bound_with_options(
+                    &items,
+                    weight_limit,
+                    &solve_options(time_limit)?,
+                );
+                eprintln!(
+                    "Warm start explored {} nodes instead of {} nodes, saving {} nodes",
+                    warm_statistics.explored_nodes,
+                    statistics.explored_nodes,
+                    statistics
+                        .explored_nodes
+                        .saturating_sub(warm_statistics.explored_nodes)
+                );
+            }
+            warm_result
+        }
+        None => {
+            aud2::knapsack::branch_and_bound_with_options(
+                &items,
+                weight_limit,
+                &solve_options(time_limit)?,
+            )
+            .0
+        }
+    };
+    print_solve_result(output, result, weight_limit)
+}
+
+/// CLI wrapper for [aud2::knapsack::meet_in_the_middle].
+fn knapsack_meet_in_the_middle_cli(
+    cli_args: cli::KnapsackMeetInTheMiddle,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::KnapsackMeetInTheMiddle {
+        items_csv,
+        format,
+        flipped_csv,
+        weight_limit,
+        time_limit,
+    } = cli_args;
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let (result, _) = aud2::knapsack::meet_in_the_middle_with_options(
+        &items,
+        weight_limit,
+        &solve_options(time_limit)?,
+    )
+    .context("Meet in the middle")?;
+    print_solve_result(output, result, weight_limit)
+}
+
+/// CLI wrapper for [aud2::generator::generate].
+fn generate_knapsack_cli(
+    cli_args: cli::GenerateKnapsack,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::GenerateKnapsack {
+        class,
+        item_count,
+        range,
+        seed,
+        spanner_base,
+        spanner_items,
+        spanner_multiplier,
+        output_csv,
+        flipped_csv,
+    } = cli_args;
+    let class = match class {
+        InstanceClass::Spanner { .. } => InstanceClass::Spanner {
+            base: Box::new(spanner_base),
+            spanner_items,
+            max_multiplier: spanner_multiplier,
+        },
+        class => class,
+    };
+    let items = aud2::generator::generate(&class, item_count, range, seed)?;
+    eprintln!(
+        "Generated {} items with total weight {}",
+        items.len(),
+        items.iter().map(|item| item.weight).sum::<u64>()
     );
+    let serialized = match output {
+        OutputFormat::Json => serde_json::to_string_pretty(&items)? + "\n",
+        OutputFormat::Text | OutputFormat::Csv => {
+            write_csv(&items, flipped_csv).context("Write items")?
+        }
+    };
+    match output_csv {
+        Some(filename) => {
+            fs::write(&filename, serialized).with_context(|| format!("Write file {}", filename))?
+        }
+        None => print!("{}", serialized),
+    }
     Ok(())
 }
 
+/// CLI wrapper for [aud2::compare::compare_algorithms].
+fn knapsack_compare_cli(
+    cli_args: cli::KnapsackCompare,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::KnapsackCompare {
+        items_csv,
+        format,
+        flipped_csv,
+        weight_limit,
+        k,
+        class,
+        item_count,
+        range,
+        instances,
+        seed,
+        time_limit,
+        export_csv,
+    } = cli_args;
+    let time_limit = Duration::try_from_secs_f64(time_limit).context("Invalid --time-limit")?;
+
+    // Collect the instances to compare on, i.e. their name, items and weight limit
+    let instances: Vec<(String, Vec<Item>, u64)> = match (items_csv, class) {
+        (Some(items_csv), None) => {
+            let file_instances = read_instances(&items_csv, format, flipped_csv, weight_limit)
+                .context("Read items")?;
+            let instance_count = file_instances.len();
+            file_instances
+                .into_iter()
+                .enumerate()
+                .map(|(index, instance)| {
+                    let name = if instance_count == 1 {
+                        items_csv.clone()
+                    } else {
+                        format!("{} #{}", items_csv, index + 1)
+                    };
+                    (name, instance.items, instance.weight_limit)
+                })
+                .collect()
+        }
+        (None, Some(class)) => (seed..seed + instances)
+            .map(|seed| {
+                let items = aud2::generator::generate(&class, item_count, range, seed)?;
+                let weight_limit = weight_limit
+                    .unwrap_or_else(|| items.iter().map(|item| item.weight).sum::<u64>() / 2);
+                Ok((format!("{} seed={}", class, seed), items, weight_limit))
+            })
+            .collect::<anyhow::Result<_>>()?,
+        _ => anyhow::bail!("Either --items-csv or --class is required"),
+    };
+
+    let mut rows = Vec::new();
+    let mut mismatches = Vec::new();
+    for (instance, items, weight_limit) in instances {
+        let report = aud2::compare::compare_algorithms(&items, weight_limit, k, time_limit);
+        if let Some(mismatch) = report.mismatch {
+            mismatches.push(format!("{}: {}", instance, mismatch));
+        }
+        rows.extend(
+            report
+                .comparisons
+                .into_iter()
+                .map(|comparison| ComparisonRow {
+                    instance: instance.clone(),
+                    weight_limit,
+                    algorithm: comparison.algorithm,
+                    run: Some(ComparisonRun {
+                        profit: comparison.profit,
+                        weight: comparison.weight,
+                        gap: comparison.gap,
+                        ratio: comparison.ratio,
+                        runtime: comparison.runtime,
+                        counter: comparison.counter.name,
+                        count: comparison.counter.value,
+                        memory_bytes: comparison.memory,
+                    }),
+                    skipped: None,
+                }),
+        );
+        rows.extend(report.skipped.into_iter().map(|skipped| ComparisonRow {
+            instance: instance.clone(),
+            weight_limit,
+            algorithm: skipped.algorithm,
+            run: None,
+            skipped: Some(skipped.reason),
+        }));
+    }
+    let table = ComparisonTable { rows };
+    print_output(output, &table)?;
+
+    if let Some(filename) = export_csv {
+        let mut csv_writer = csv::Writer::from_writer(Vec::new());
+        table.write_csv(&mut csv_writer)?;
+        fs::write(&filename, csv_writer.into_inner()?)
+            .with_context(|| format!("Write csv file {}", filename))?;
+    }
+    if mismatches.is_empty().not() {
+        anyhow::bail!("Wrong exact algorithm, {}", mismatches.join("; "));
+    }
+    Ok(())
+}
+
+/// CLI wrapper for [aud2::knapsack::verify].
+fn knapsack_verify_cli(cli_args: cli::KnapsackVerify, output: OutputFormat) -> anyhow::Result<()> {
+    let cli::KnapsackVerify {
+        items_csv,
+        format,
+        flipped_csv,
+        weight_limit,
+        prove_optimality,
+        ids,
+    } = cli_args;
+    let Instance {
+        items,
+        weight_limit,
+    } = read_instance(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let verification = aud2::knapsack::verify(&items, weight_limit, &ids, prove_optimality);
+    print_output(
+        output,
+        &VerificationReport::new(&verification, weight_limit),
+    )?;
+    if verification.is_feasible().not() {
+        anyhow::bail!("Solution is not feasible");
+    }
+    Ok(())
+}
+
+/// CLI wrapper for [aud2::knapsack::ilp::IlpModel].
+fn knapsack_export_cli(cli_args: cli::KnapsackExport) -> anyhow::Result<()> {
+    let cli::KnapsackExport {
+        items_csv,
+        format,
+        flipped_csv,
+        weight_limit,
+        model_format,
+        output_file,
+    } = cli_args;
+    let models =
+        read_models(&items_csv, format, flipped_csv, weight_limit).context("Read items")?;
+    let model = first_instance(&items_csv, models);
+    let mut buffer = Vec::new();
+    match model_format {
+        IlpFormat::Lp => model.write_lp(&mut buffer),
+        IlpFormat::Mps => model.write_mps(&mut buffer),
+    }
+    .context("Export model")?;
+    match output_file {
+        Some(filename) => {
+            fs::write(&filename, buffer).with_context(|| format!("Write file {}", filename))?
+        }
+        None => print!("{}", String::from_utf8(buffer)?),
+    }
+    Ok(())
+}
+
+/// CLI wrapper for [aud2::knapsack::ilp::read_solution].
+fn knapsack_import_solution_cli(
+    cli_args: cli::KnapsackImportSolution,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let cli::KnapsackImportSolution { solution_file } = cli_args;
+    let solution = fs::read_to_string(&solution_file)
+        .with_context(|| format!("Open solution file {}", solution_file))?;
+    let ids = aud2::knapsack::ilp::read_solution(&solution);
+    print_output(output, &ImportedSolution { ids })
+}
+
 // Other helper functions
 
 /// Transpose a Vec<Vec<T>>, i.e. flip rows and columns. All inner Vec's must have the same length.
@@ -243,19 +835,285 @@ fn flip_csv(csv: String) -> String {
         .join("\n")
 }
 
-/// Read and parse the csv file `filename` into a `Vec<T>`.
-fn read_csv<T>(filename: &str, flipped: bool) -> anyhow::Result<Vec<T>>
+/// Read the knapsack instances in the file `filename` as integer linear programs. The format is detected from the file
+/// if `format` is `None`. Csv files contain no weight limit, so `weight_limit` is required for them. For other formats
+/// it overrides the weight limit of the file.
+fn read_models(
+    filename: &str,
+    format: Option<InstanceFormat>,
+    flipped: bool,
+    weight_limit: Option<u64>,
+) -> anyhow::Result<Vec<IlpModel>> {
+    let content =
+        fs::read_to_string(filename).with_context(|| format!("Open file {}", filename))?;
+    let format = format.unwrap_or_else(|| detect_format(filename, &content));
+    let from_instance =
+        |instance: Instance| IlpModel::from_items(&instance.items, instance.weight_limit);
+    let mut models = match format {
+        InstanceFormat::Csv => {
+            let weight_limit = weight_limit.context("--weight-limit is required for a csv file")?;
+            let items: Vec<Item> = parse_csv(content, flipped).context("Parse csv file")?;
+            vec![IlpModel::from_items(&items, weight_limit)]
+        }
+        InstanceFormat::Pisinger => parse_pisinger(&content)
+            .context("Parse Pisinger file")?
+            .into_iter()
+            .map(from_instance)
+            .collect(),
+        InstanceFormat::OrLibrary => parse_or_library(&content).context("Parse OR-Library file")?,
+        InstanceFormat::Json => vec![parse_json_model(&content).context("Parse json file")?],
+        InstanceFormat::Toml => vec![parse_toml_model(&content).context("Parse toml file")?],
+    };
+    if let Some(weight_limit) = weight_limit {
+        for model in &mut models {
+            if model.weight_limits.len() != 1 {
+                anyhow::bail!(
+                    "--weight-limit is not supported for knapsacks with multiple dimensions"
+                );
+            }
+            model.weight_limits[0] = weight_limit;
+        }
+    }
+    Ok(models)
+}
+
+/// Read the 0/1 knapsack instances in the file `filename`, see [read_models].
+fn read_instances(
+    filename: &str,
+    format: Option<InstanceFormat>,
+    flipped: bool,
+    weight_limit: Option<u64>,
+) -> anyhow::Result<Vec<Instance>> {
+    read_models(filename, format, flipped, weight_limit)?
+        .iter()
+        .map(|model| {
+            model.to_instance().context(
+                "Only ks-export supports knapsacks with multiple dimensions or bounded items",
+            )
+        })
+        .collect()
+}
+
+/// Read the first 0/1 knapsack instance in the file `filename`, see [read_models].
+fn read_instance(
+    filename: &str,
+    format: Option<InstanceFormat>,
+    flipped: bool,
+    weight_limit: Option<u64>,
+) -> anyhow::Result<Instance> {
+    let instances = read_instances(filename, format, flipped, weight_limit)?;
+    Ok(first_instance(filename, instances))
+}
+
+/// Returns the first of the non-empty `instances` read from the file `filename`. Notes on stderr if the others are
+/// ignored.
+fn first_instance<T>(filename: &str, instances: Vec<T>) -> T {
+    if instances.len() > 1 {
+        eprintln!(
+            "{} contains {} instances, using the first one",
+            filename,
+            instances.len()
+        );
+    }
+    instances
+        .into_iter()
+        .next()
+        .expect("parsers return at least one instance")
+}
+
+/// Detects the format of the instance file `filename` from its extension or, if that is ambiguous, its `content`.
+fn detect_format(filename: &str, content: &str) -> InstanceFormat {
+    match Path::new(filename).extension().and_then(OsStr::to_str) {
+        Some("json") => InstanceFormat::Json,
+        Some("toml") => InstanceFormat::Toml,
+        // Pisinger's instances are distributed as .csv files, too
+        _ if is_pisinger(content) => InstanceFormat::Pisinger,
+        _ if is_or_library(content) => InstanceFormat::OrLibrary,
+        _ => InstanceFormat::Csv,
+    }
+}
+
+/// Parse the content `csv` of a csv file into a `Vec<T>`.
+fn parse_csv<T>(mut csv: String, flipped: bool) -> anyhow::Result<Vec<T>>
 where
     T: serde::de::DeserializeOwned,
 {
-    let mut csv =
-        fs::read_to_string(filename).with_context(|| format!("Open csv file {}", filename))?;
     if flipped {
         csv = flip_csv(csv);
     }
     let mut csv_reader = csv::Reader::from_reader(csv.as_bytes());
     let items: Result<Vec<T>, _> = csv_reader.deserialize::<T>().collect();
-    items.context("Parse csv file")
+    Ok(items?)
+}
+
+/// Write `items` as csv (id, profit, weight) into a String, which can be read by [parse_csv].
+fn write_csv(items: &[Item], flipped: bool) -> anyhow::Result<String> {
+    let mut csv_writer = csv::Writer::from_writer(Vec::new());
+    csv_writer.write_record(["id", "profit", "weight"])?;
+    for item in items {
+        csv_writer.write_record([
+            item.id.to_string(),
+            item.profit.to_string(),
+            item.weight.to_string(),
+        ])?;
+    }
+    let csv = String::from_utf8(csv_writer.into_inner()?)?;
+    let csv = if flipped { flip_csv(csv) + "\n" } else { csv };
+    Ok(csv)
+}
+
+/// Returns the numbers of a subset sum or partition subcommand, either the positional `numbers` or the ones in the file
+/// `numbers_file`, which is read from stdin if it is `-`. See [parse_numbers] for the format and the labels.
+fn read_numbers<T>(
+    numbers: Vec<T>,
+    numbers_file: Option<String>,
+) -> anyhow::Result<(Vec<T>, Option<Vec<String>>)>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let Some(numbers_file) = numbers_file else {
+        return Ok((numbers, None));
+    };
+    if numbers.is_empty().not() {
+        anyhow::bail!("Pass the numbers either as arguments or via --numbers-file");
+    }
+    let content = if numbers_file == "-" {
+        io::read_to_string(io::stdin()).context("Read numbers from stdin")?
+    } else {
+        fs::read_to_string(&numbers_file)
+            .with_context(|| format!("Read numbers file {}", numbers_file))?
+    };
+    parse_numbers(&content).with_context(|| match numbers_file.as_str() {
+        "-" => "Parse numbers from stdin".to_string(),
+        _ => format!("Parse numbers file {}", numbers_file),
+    })
+}
+
+/// Parses the numbers in `content`, which are separated by newlines or commas.
+///
+/// Alternatively, the content is a csv file with a number and a label in each line. Its first line is either the header
+/// naming the columns `number` and (optionally) `label`, or the first number and its label. Without a header, each line
+/// must have exactly these two fields and a label must not be a number, so that a mistyped number is not taken as
+/// label. The labels are returned with the numbers.
+fn parse_numbers<T>(content: &str) -> anyhow::Result<(Vec<T>, Option<Vec<String>>)>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let csv_reader = |has_headers: bool| {
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .has_headers(has_headers)
+            // The number of fields of lines without header is checked below, to report it with a clear message
+            .flexible(has_headers.not())
+            .from_reader(content.as_bytes())
+    };
+    let is_number = |field: &str| field.parse::<T>().is_ok();
+    let first_record = csv_reader(false)
+        .records()
+        .next()
+        .transpose()?
+        .unwrap_or_default();
+    let is_header = first_record
+        .iter()
+        .any(|field| field.eq_ignore_ascii_case("number"))
+        && first_record.iter().all(|field| {
+            field.eq_ignore_ascii_case("number") || field.eq_ignore_ascii_case("label")
+        });
+    let is_labeled =
+        first_record.len() == 2 && is_number(&first_record[0]) && is_number(&first_record[1]).not();
+    if is_header.not() && is_labeled.not() {
+        let numbers = content
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|field| field.is_empty().not())
+            .map(|field| {
+                field
+                    .parse()
+                    .with_context(|| format!("Invalid number {:?}", field))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        return Ok((numbers, None));
+    }
+
+    let mut reader = csv_reader(is_header);
+    let (number_column, label_column) = if is_header {
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+        };
+        (column("number").expect("checked above"), column("label"))
+    } else {
+        (0, Some(1))
+    };
+
+    let mut numbers = Vec::new();
+    let mut labels = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, csv::Position::line);
+        if is_header.not() {
+            if record.len() != 2 {
+                anyhow::bail!(
+                    "Line {} has {} fields, but a number and a label are expected",
+                    line,
+                    record.len()
+                );
+            }
+            if is_number(&record[1]) {
+                anyhow::bail!(
+                    "The label {:?} in line {} is a number, separate numbers by newlines or commas only",
+                    &record[1],
+                    line
+                );
+            }
+        }
+        let field = record
+            .get(number_column)
+            .with_context(|| format!("Missing number in line {}", line))?;
+        numbers.push(
+            field
+                .parse()
+                .with_context(|| format!("Invalid number {:?} in line {}", field, line))?,
+        );
+        if let Some(label_column) = label_column {
+            labels.push(record.get(label_column).unwrap_or_default().to_string());
+        }
+    }
+    Ok((numbers, label_column.map(|_| labels)))
+}
+
+/// Returns an error if more than one of the flags `--count`, `--at-most`, `--nearest` and `--min-count`/`--max-count`
+/// of the subset sum subcommands is set.
+fn check_exclusive_flags(
+    count: bool,
+    at_most: bool,
+    nearest: bool,
+    limit_count: bool,
+) -> anyhow::Result<()> {
+    if [count, at_most, nearest, limit_count]
+        .into_iter()
+        .filter(|&flag| flag)
+        .count()
+        > 1
+    {
+        anyhow::bail!(
+            "Only one of --count, --at-most, --nearest and --min-count/--max-count can be used"
+        );
+    }
+    Ok(())
+}
+
+/// Converts `numbers` and `sum` to u64, if none of them is negative.
+fn to_unsigned(numbers: &[i64], sum: i64) -> Option<(Vec<u64>, u64)> {
+    let numbers = numbers
+        .iter()
+        .map(|&number| u64::try_from(number).ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((numbers, u64::try_from(sum).ok()?))
 }
 
 /// Initialize the logger.
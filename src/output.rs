@@ -0,0 +1,697 @@
+//! Printing the results of the subcommands in every [OutputFormat]. Each subcommand collects its result in a type
+//! implementing [Output], whose fields are the stable schema of the json and csv output.
+
+use std::time::Duration;
+use std::{fmt, io};
+
+use fraction::Fraction;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+
+use aud2::knapsack::{
+    serialize_fraction, Item, Optimality, PartialPackedItem, Solution, SolveResult, SolveStatus,
+    Verification,
+};
+use aud2::partition::Partition;
+use aud2::subset_sum::Subset;
+
+use crate::cli::OutputFormat;
+
+/// The result of a subcommand, which can be printed in every [OutputFormat]. Json is written via [Serialize].
+pub(crate) trait Output: Serialize {
+    /// Prints the result as human readable text.
+    fn print_text(&self);
+
+    /// Writes the result as csv table, including a header.
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()>;
+}
+
+/// Prints `result` to stdout in the format `output`.
+pub(crate) fn print_output(output: OutputFormat, result: &impl Output) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Text => result.print_text(),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(result)?),
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(io::stdout());
+            result.write_csv(&mut writer)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// The result of a knapsack solver that accepts [SolveOptions](aud2::knapsack::SolveOptions), which may have been
+/// stopped early by `--time-limit`.
+#[derive(Serialize)]
+pub(crate) struct SolveOutput {
+    /// The best knapsack found.
+    #[serde(flatten)]
+    pub(crate) solution: Solution,
+    /// Whether the solver ran until the end: `completed`, `cancelled` or `deadline_exceeded`.
+    pub(crate) status: &'static str,
+    /// Whether the knapsack is guaranteed to be optimal. This is only the case for exact solvers that ran until the
+    /// end.
+    pub(crate) optimal: bool,
+}
+
+impl SolveOutput {
+    pub(crate) fn new(result: SolveResult<Item>, weight_limit: u64) -> Self {
+        SolveOutput {
+            solution: Solution::new(result.knapsack, weight_limit),
+            status: match result.status {
+                SolveStatus::Completed => "completed",
+                SolveStatus::Cancelled => "cancelled",
+                SolveStatus::DeadlineExceeded => "deadline_exceeded",
+            },
+            optimal: result.optimal,
+        }
+    }
+}
+
+impl Output for SolveOutput {
+    fn print_text(&self) {
+        println!("Knapsack:");
+        println!("id's: {:?}", self.solution.ids());
+        println!("Total profit: {}", self.solution.profit);
+        println!(
+            "Total weight {} of allowed weight limit {}",
+            self.solution.weight, self.solution.weight_limit
+        );
+        if self.status != "completed" {
+            println!(
+                "Solver stopped early ({}), the knapsack may not be optimal",
+                self.status
+            );
+        }
+    }
+
+    /// Writes a single row like [Verification], so the totals, status and optimality are also written for an empty
+    /// knapsack. The id's are separated by spaces.
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record([
+            "ids",
+            "profit",
+            "weight",
+            "weight_limit",
+            "status",
+            "optimal",
+        ])?;
+        writer.write_record([
+            self.solution.ids().iter().join(" "),
+            self.solution.profit.to_string(),
+            self.solution.weight.to_string(),
+            self.solution.weight_limit.to_string(),
+            self.status.to_string(),
+            self.optimal.to_string(),
+        ])
+    }
+}
+
+/// The result of [aud2::knapsack::fractional_greedy].
+#[derive(Serialize)]
+pub(crate) struct FractionalSolution<'a> {
+    /// The (partially) packed items.
+    pub(crate) items: Vec<PartialPackedItem<'a, Item>>,
+    /// Total profit of the packed items.
+    #[serde(serialize_with = "serialize_fraction")]
+    pub(crate) profit: Fraction,
+}
+
+impl<'a> FractionalSolution<'a> {
+    pub(crate) fn new(items: Vec<PartialPackedItem<'a, Item>>) -> Self {
+        let profit = items.iter().map(PartialPackedItem::effective_profit).sum();
+        FractionalSolution { items, profit }
+    }
+}
+
+impl Output for FractionalSolution<'_> {
+    fn print_text(&self) {
+        println!("Knapsack:");
+        for chosen_item in &self.items {
+            println!(
+                "id={:<2} take_ratio={:<3}",
+                chosen_item.item.id, chosen_item.take_ratio
+            );
+        }
+        println!("total_profit={} = approx. {:.3}", self.profit, self.profit);
+    }
+
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record(["id", "profit", "weight", "take_ratio"])?;
+        for chosen_item in &self.items {
+            writer.write_record([
+                chosen_item.item.id.to_string(),
+                chosen_item.item.profit.to_string(),
+                chosen_item.item.weight.to_string(),
+                chosen_item.take_ratio.to_string(),
+            ])?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of the subset sum subcommands, with unsigned or signed numbers `N`.
+#[derive(Serialize)]
+pub(crate) struct SubsetSumResult<N> {
+    /// The input numbers.
+    pub(crate) numbers: Vec<N>,
+    /// The sum that should be reached.
+    pub(crate) sum: N,
+    /// Whether `sum` is reachable.
+    pub(crate) reachable: bool,
+    /// Indices of the numbers summing up to `sum`, if it is reachable and the solver returns a witness.
+    pub(crate) witness: Option<Vec<usize>>,
+    /// Labels of the numbers summing up to `sum`, if it is reachable and the numbers are labeled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) witness_labels: Option<Vec<String>>,
+}
+
+impl<N: Copy> SubsetSumResult<N> {
+    /// Creates the result with the `labels` of the `numbers`, if they are labeled.
+    pub(crate) fn new(
+        numbers: Vec<N>,
+        sum: N,
+        witness: Option<Vec<usize>>,
+        labels: Option<Vec<String>>,
+    ) -> Self {
+        SubsetSumResult {
+            numbers,
+            sum,
+            reachable: witness.is_some(),
+            witness_labels: witness_labels(witness.as_deref(), labels),
+            witness,
+        }
+    }
+
+    /// Creates the result of a solver that only decides whether `sum` is `reachable`, without a witness.
+    pub(crate) fn without_witness(numbers: Vec<N>, sum: N, reachable: bool) -> Self {
+        SubsetSumResult {
+            numbers,
+            sum,
+            reachable,
+            witness: None,
+            witness_labels: None,
+        }
+    }
+
+    /// The numbers summing up to `sum`, if it is reachable.
+    fn witness_numbers(&self) -> Option<Vec<N>> {
+        self.witness
+            .as_ref()
+            .map(|witness| witness.iter().map(|&index| self.numbers[index]).collect())
+    }
+}
+
+impl<N: Copy + Serialize + fmt::Debug + fmt::Display> Output for SubsetSumResult<N> {
+    fn print_text(&self) {
+        println!("Input numbers: {:?}", self.numbers);
+        println!("Sum {} reachable: {}", self.sum, self.reachable);
+        if let (Some(witness), Some(witness_numbers)) = (&self.witness, self.witness_numbers()) {
+            println!("Indices: {:?}", witness);
+            println!("Numbers: {:?}", witness_numbers);
+        }
+        if let Some(witness_labels) = &self.witness_labels {
+            println!("Labels: {:?}", witness_labels);
+        }
+    }
+
+    /// Writes a single row. The indices and numbers of the witness are separated by spaces, its labels by semicolons.
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record(["sum", "reachable", "indices", "numbers", "labels"])?;
+        writer.write_record([
+            self.sum.to_string(),
+            self.reachable.to_string(),
+            self.witness.iter().flatten().join(" "),
+            self.witness_numbers().iter().flatten().join(" "),
+            self.witness_labels.iter().flatten().join("; "),
+        ])
+    }
+}
+
+/// The result of the subset sum subcommands that search a subset sum close to a target.
+#[derive(Serialize)]
+pub(crate) struct ClosestSubsetResult {
+    /// The input numbers.
+    pub(crate) numbers: Vec<u64>,
+    /// The sum that should be approached.
+    pub(crate) target: u64,
+    /// The sum of the found subset.
+    pub(crate) sum: u64,
+    /// Indices of the numbers of the found subset.
+    pub(crate) witness: Vec<usize>,
+    /// Labels of the numbers of the found subset, if the numbers are labeled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) witness_labels: Option<Vec<String>>,
+}
+
+impl ClosestSubsetResult {
+    /// Creates the result for the found `subset`, with the `labels` of the `numbers` if they are labeled.
+    pub(crate) fn new(
+        numbers: Vec<u64>,
+        target: u64,
+        subset: Subset,
+        labels: Option<Vec<String>>,
+    ) -> Self {
+        ClosestSubsetResult {
+            numbers,
+            target,
+            sum: subset.sum,
+            witness_labels: witness_labels(Some(&subset.indices), labels),
+            witness: subset.indices,
+        }
+    }
+
+    /// The numbers of the found subset.
+    fn witness_numbers(&self) -> Vec<u64> {
+        self.witness
+            .iter()
+            .map(|&index| self.numbers[index])
+            .collect()
+    }
+}
+
+impl Output for ClosestSubsetResult {
+    fn print_text(&self) {
+        println!("Input numbers: {:?}", self.numbers);
+        println!("Target sum: {}", self.target);
+        println!("Found sum: {}", self.sum);
+        println!("Indices: {:?}", self.witness);
+        println!("Numbers: {:?}", self.witness_numbers());
+        if let Some(witness_labels) = &self.witness_labels {
+            println!("Labels: {:?}", witness_labels);
+        }
+    }
+
+    /// Writes a single row. The indices and numbers of the witness are separated by spaces, its labels by semicolons.
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record(["target", "sum", "indices", "numbers", "labels"])?;
+        writer.write_record([
+            self.target.to_string(),
+            self.sum.to_string(),
+            self.witness.iter().join(" "),
+            self.witness_numbers().iter().join(" "),
+            self.witness_labels.iter().flatten().join("; "),
+        ])
+    }
+}
+
+/// The result of the subset sum subcommands with `--count`.
+#[derive(Serialize)]
+pub(crate) struct SubsetSumCount {
+    /// The input numbers.
+    pub(crate) numbers: Vec<u64>,
+    /// The sum that should be reached.
+    pub(crate) sum: u64,
+    /// The number of subsets (by index) of `numbers` adding up to `sum`, modulo `modulus` if given.
+    pub(crate) count: u128,
+    /// The modulus of `count`, if any.
+    pub(crate) modulus: Option<u128>,
+}
+
+impl Output for SubsetSumCount {
+    fn print_text(&self) {
+        println!("Input numbers: {:?}", self.numbers);
+        match self.modulus {
+            Some(modulus) => println!(
+                "Subsets with sum {}: {} (mod {})",
+                self.sum, self.count, modulus
+            ),
+            None => println!("Subsets with sum {}: {}", self.sum, self.count),
+        }
+    }
+
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record(["sum", "count", "modulus"])?;
+        writer.write_record([
+            self.sum.to_string(),
+            self.count.to_string(),
+            self.modulus
+                .map(|modulus| modulus.to_string())
+                .unwrap_or_default(),
+        ])
+    }
+}
+
+/// The result of the partition subcommand.
+#[derive(Serialize)]
+pub(crate) struct PartitionResult {
+    /// The input numbers.
+    pub(crate) numbers: Vec<u64>,
+    /// The parts, containing the indices of the numbers.
+    #[serde(flatten)]
+    pub(crate) partition: Partition,
+    /// The difference between the largest and the smallest sum of a part.
+    pub(crate) difference: u64,
+    /// Whether the difference is the smallest possible. `None` for heuristics.
+    pub(crate) optimal: Option<bool>,
+    /// The labels of the numbers in each part, if the numbers are labeled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) part_labels: Option<Vec<Vec<String>>>,
+}
+
+impl PartitionResult {
+    /// Creates the result with the `labels` of the `numbers`, if they are labeled.
+    pub(crate) fn new(
+        numbers: Vec<u64>,
+        partition: Partition,
+        optimal: Option<bool>,
+        labels: Option<Vec<String>>,
+    ) -> Self {
+        let part_labels = labels.map(|labels| {
+            partition
+                .parts
+                .iter()
+                .map(|part| part.iter().map(|&index| labels[index].clone()).collect())
+                .collect()
+        });
+        PartitionResult {
+            numbers,
+            difference: partition.difference(),
+            partition,
+            optimal,
+            part_labels,
+        }
+    }
+}
+
+impl Output for PartitionResult {
+    fn print_text(&self) {
+        println!("Input numbers: {:?}", self.numbers);
+        for (i, (part, sum)) in self
+            .partition
+            .parts
+            .iter()
+            .zip(&self.partition.sums)
+            .enumerate()
+        {
+            let part_numbers: Vec<u64> = part.iter().map(|&index| self.numbers[index]).collect();
+            println!(
+                "Part {}: numbers {:?} at indices {:?}, sum {}",
+                i + 1,
+                part_numbers,
+                part,
+                sum
+            );
+            if let Some(part_labels) = &self.part_labels {
+                println!("Part {} labels: {:?}", i + 1, part_labels[i]);
+            }
+        }
+        println!("Difference: {}", self.difference);
+        match self.optimal {
+            Some(optimal) => println!("Optimal: {}", optimal),
+            None => println!("Optimal: unknown"),
+        }
+    }
+
+    /// Writes one row per number with its part, numbered from 1, and its label.
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record(["index", "number", "part", "label"])?;
+        for (i, part) in self.partition.parts.iter().enumerate() {
+            for (j, &index) in part.iter().enumerate() {
+                writer.write_record([
+                    index.to_string(),
+                    self.numbers[index].to_string(),
+                    (i + 1).to_string(),
+                    self.part_labels
+                        .as_ref()
+                        .map(|part_labels| part_labels[i][j].clone())
+                        .unwrap_or_default(),
+                ])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The result of [aud2::knapsack::verify].
+#[derive(Serialize)]
+pub(crate) struct VerificationReport {
+    /// The chosen items. Unknown ids are left out and duplicate ids are only contained once.
+    pub(crate) ids: Vec<usize>,
+    /// Total profit of the chosen items.
+    pub(crate) profit: u64,
+    /// Total weight of the chosen items.
+    pub(crate) weight: u64,
+    /// The maximum allowed weight of the knapsack.
+    pub(crate) weight_limit: u64,
+    /// Upper bound for the optimal profit.
+    pub(crate) upper_bound: u64,
+    /// `upper_bound - profit`.
+    pub(crate) upper_bound_gap: u64,
+    /// Whether the solution is optimal. `None` if this was neither proven nor refuted.
+    pub(crate) optimal: Option<bool>,
+    /// The optimal profit, if known.
+    pub(crate) optimal_profit: Option<u64>,
+    /// Whether the solution is feasible.
+    pub(crate) feasible: bool,
+    /// All reasons why the solution is not feasible.
+    pub(crate) infeasibilities: Vec<String>,
+}
+
+impl VerificationReport {
+    pub(crate) fn new(verification: &Verification<Item>, weight_limit: u64) -> Self {
+        let (optimal, optimal_profit) = match verification.optimality {
+            Optimality::Optimal => (Some(true), Some(verification.profit)),
+            Optimality::NotOptimal { optimal_profit } => (Some(false), Some(optimal_profit)),
+            Optimality::Unknown => (None, None),
+        };
+        VerificationReport {
+            ids: verification.knapsack.iter().map(|item| item.id).collect(),
+            profit: verification.profit,
+            weight: verification.weight,
+            weight_limit,
+            upper_bound: verification.upper_bound,
+            upper_bound_gap: verification.upper_bound_gap(),
+            optimal,
+            optimal_profit,
+            feasible: verification.is_feasible(),
+            infeasibilities: verification
+                .infeasibilities
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl Output for VerificationReport {
+    fn print_text(&self) {
+        println!("id's: {:?}", self.ids);
+        println!("Total profit: {}", self.profit);
+        println!(
+            "Total weight {} of allowed weight limit {}",
+            self.weight, self.weight_limit
+        );
+        println!(
+            "Upper bound: {} (gap at most {})",
+            self.upper_bound, self.upper_bound_gap
+        );
+        match (self.optimal, self.optimal_profit) {
+            (Some(true), _) => println!("Optimal: true"),
+            (Some(false), Some(optimal_profit)) => {
+                println!("Optimal: false (optimal profit is {})", optimal_profit)
+            }
+            _ => println!("Optimal: unknown"),
+        }
+        println!("Feasible: {}", self.feasible);
+        for infeasibility in &self.infeasibilities {
+            println!("  {}", infeasibility);
+        }
+    }
+
+    /// Writes a single row. The id's are separated by spaces and the infeasibilities by semicolons.
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record([
+            "ids",
+            "profit",
+            "weight",
+            "weight_limit",
+            "upper_bound",
+            "upper_bound_gap",
+            "optimal",
+            "optimal_profit",
+            "feasible",
+            "infeasibilities",
+        ])?;
+        writer.write_record([
+            self.ids.iter().join(" "),
+            self.profit.to_string(),
+            self.weight.to_string(),
+            self.weight_limit.to_string(),
+            self.upper_bound.to_string(),
+            self.upper_bound_gap.to_string(),
+            self.optimal
+                .map(|optimal| optimal.to_string())
+                .unwrap_or_default(),
+            self.optimal_profit
+                .map(|optimal_profit| optimal_profit.to_string())
+                .unwrap_or_default(),
+            self.feasible.to_string(),
+            self.infeasibilities.join("; "),
+        ])
+    }
+}
+
+/// One row of [ComparisonTable], i.e. the result of one algorithm on one instance.
+#[derive(Serialize)]
+pub(crate) struct ComparisonRow {
+    /// Name of the instance.
+    pub(crate) instance: String,
+    /// The maximum allowed weight of the knapsack.
+    pub(crate) weight_limit: u64,
+    /// Name of the algorithm.
+    pub(crate) algorithm: String,
+    /// The result of the algorithm. `None` if it was skipped.
+    #[serde(flatten)]
+    pub(crate) run: Option<ComparisonRun>,
+    /// Why the algorithm was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) skipped: Option<String>,
+}
+
+/// The result of an algorithm that was run in [ComparisonRow].
+#[derive(Serialize)]
+pub(crate) struct ComparisonRun {
+    /// Total profit of the chosen items.
+    pub(crate) profit: u64,
+    /// Total weight of the chosen items.
+    pub(crate) weight: u64,
+    /// Difference between the optimal profit and `profit`, if known.
+    pub(crate) gap: Option<u64>,
+    /// `profit / optimal profit`, if known.
+    pub(crate) ratio: Option<f64>,
+    /// How long the algorithm took.
+    #[serde(rename = "runtime_us", serialize_with = "serialize_micros")]
+    pub(crate) runtime: Duration,
+    /// What is counted by `count`, e.g. `nodes`.
+    pub(crate) counter: &'static str,
+    /// A counter for the work done by the algorithm.
+    pub(crate) count: u64,
+    /// How many bytes the algorithm needed at most, if it needs more than linear memory in the number of items.
+    pub(crate) memory_bytes: Option<u64>,
+}
+
+/// The result of [aud2::compare::compare_algorithms] on one or more instances.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub(crate) struct ComparisonTable {
+    pub(crate) rows: Vec<ComparisonRow>,
+}
+
+impl Output for ComparisonTable {
+    fn print_text(&self) {
+        for ((instance, weight_limit), rows) in &self
+            .rows
+            .iter()
+            .group_by(|row| (&row.instance, row.weight_limit))
+        {
+            println!("Instance {} with weight limit {}:", instance, weight_limit);
+            println!(
+                "{:<20} {:>12} {:>12} {:>12} {:>7} {:>12} {:>12}  counter",
+                "algorithm", "profit", "weight", "gap", "ratio", "runtime", "memory"
+            );
+            for row in rows {
+                let Some(run) = &row.run else {
+                    println!(
+                        "{:<20} skipped: {}",
+                        row.algorithm,
+                        row.skipped.as_deref().unwrap_or_default()
+                    );
+                    continue;
+                };
+                println!(
+                    "{:<20} {:>12} {:>12} {:>12} {:>7} {:>12} {:>12}  {}={}",
+                    row.algorithm,
+                    run.profit,
+                    run.weight,
+                    run.gap.map_or("-".to_string(), |gap| gap.to_string()),
+                    run.ratio
+                        .map_or("-".to_string(), |ratio| format!("{:.4}", ratio)),
+                    format!("{:.3?}", run.runtime),
+                    run.memory_bytes
+                        .map_or("-".to_string(), |memory_bytes| format!(
+                            "{} B",
+                            memory_bytes
+                        )),
+                    run.counter,
+                    run.count
+                );
+            }
+            println!();
+        }
+    }
+
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record([
+            "instance",
+            "weight_limit",
+            "algorithm",
+            "profit",
+            "weight",
+            "gap",
+            "ratio",
+            "runtime_us",
+            "counter",
+            "count",
+            "memory_bytes",
+            "skipped",
+        ])?;
+        for row in &self.rows {
+            let run = row.run.as_ref();
+            writer.write_record([
+                row.instance.clone(),
+                row.weight_limit.to_string(),
+                row.algorithm.clone(),
+                run.map(|run| run.profit.to_string()).unwrap_or_default(),
+                run.map(|run| run.weight.to_string()).unwrap_or_default(),
+                run.and_then(|run| run.gap)
+                    .map(|gap| gap.to_string())
+                    .unwrap_or_default(),
+                run.and_then(|run| run.ratio)
+                    .map(|ratio| ratio.to_string())
+                    .unwrap_or_default(),
+                run.map(|run| run.runtime.as_micros().to_string())
+                    .unwrap_or_default(),
+                run.map(|run| run.counter.to_string()).unwrap_or_default(),
+                run.map(|run| run.count.to_string()).unwrap_or_default(),
+                run.and_then(|run| run.memory_bytes)
+                    .map(|memory_bytes| memory_bytes.to_string())
+                    .unwrap_or_default(),
+                row.skipped.clone().unwrap_or_default(),
+            ])?;
+        }
+        Ok(())
+    }
+}
+
+/// The id's read from the solution file of a MILP solver.
+#[derive(Serialize)]
+pub(crate) struct ImportedSolution {
+    /// The id's of the chosen items. Items taken multiple times are repeated.
+    pub(crate) ids: Vec<usize>,
+}
+
+impl Output for ImportedSolution {
+    fn print_text(&self) {
+        println!("id's: {:?}", self.ids);
+    }
+
+    fn write_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> csv::Result<()> {
+        writer.write_record(["id"])?;
+        for id in &self.ids {
+            writer.write_record([id.to_string()])?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the labels of the numbers at the indices `witness`, if there is a witness and the numbers have `labels`.
+fn witness_labels(witness: Option<&[usize]>, labels: Option<Vec<String>>) -> Option<Vec<String>> {
+    let (witness, labels) = (witness?, labels?);
+    Some(witness.iter().map(|&index| labels[index].clone()).collect())
+}
+
+/// Serializes `duration` as whole microseconds.
+fn serialize_micros<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_micros())
+}
@@ -0,0 +1,392 @@
+//! Solving of the [partition problem](https://en.wikipedia.org/wiki/Partition_problem).
+//!
+//! From Wikipedia: "The partition problem is the task of deciding whether a given multiset S of positive integers can
+//! be partitioned into two subsets S1 and S2 such that the sum of the numbers in S1 equals the sum of the numbers in
+//! S2." The [multiway number partitioning](https://en.wikipedia.org/wiki/Multiway_number_partitioning) generalizes
+//! this to k subsets with sums as equal as possible.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::ops::Not;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::knapsack::{Progress, SolveOptions, SolveStatus};
+use crate::subset_sum::{closest_subset_sum_vec, subset_sum_bitset};
+
+/// A partition of numbers into parts. The numbers are referred to by their index.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Partition {
+    /// The indices of the numbers in each part, in ascending order.
+    pub parts: Vec<Vec<usize>>,
+    /// The sum of the numbers in each part.
+    pub sums: Vec<u64>,
+}
+
+impl Partition {
+    /// Creates the partition of `numbers` into `parts`, which contain the indices of the numbers.
+    ///
+    /// Panics if the sum of a part does not fit into an u64. This can not happen if the parts are disjoint and the total
+    /// of `numbers` fits into an u64, which the functions of this module check beforehand.
+    pub fn new(numbers: &[u64], mut parts: Vec<Vec<usize>>) -> Self {
+        for part in &mut parts {
+            part.sort_unstable();
+        }
+        let sums = parts
+            .iter()
+            .map(|part| part.iter().map(|&index| numbers[index]).sum())
+            .collect();
+        Partition { parts, sums }
+    }
+
+    /// Returns the difference between the largest and the smallest sum of a part.
+    pub fn difference(&self) -> u64 {
+        let max = self.sums.iter().max().copied().unwrap_or(0);
+        let min = self.sums.iter().min().copied().unwrap_or(0);
+        max - min
+    }
+
+    /// Returns whether all parts have the same sum.
+    pub fn is_perfect(&self) -> bool {
+        self.difference() == 0
+    }
+}
+
+/// Decides whether `numbers` can be split into two parts with equal sums, via
+/// [subset sum](crate::subset_sum::subset_sum_bitset) with half of the total sum.
+///
+/// Returns a [SumOverflowError] if the total sum does not fit into an u64.
+///
+/// ```
+/// use aud2::partition::can_partition;
+///
+/// assert_eq!(can_partition(&[3, 1, 1, 2, 2, 1]), Ok(true));
+/// assert_eq!(can_partition(&[3, 1, 1, 2, 2]), Ok(false));
+/// assert!(can_partition(&[u64::MAX, 1]).is_err());
+/// ```
+pub fn can_partition(numbers: &[u64]) -> Result<bool, SumOverflowError> {
+    let total = total_sum(numbers)?;
+    Ok(total % 2 == 0 && subset_sum_bitset(numbers, total / 2))
+}
+
+/// Splits `numbers` into two parts whose sums differ as little as possible, via
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming).
+///
+/// The first part is the subset with the largest sum not exceeding half of the total sum, the second part contains the
+/// remaining numbers. So the difference is 0 if a perfect split exists.
+///
+/// Returns a [SumOverflowError] if the total sum does not fit into an u64.
+///
+/// ```
+/// use aud2::partition::balanced_partition;
+///
+/// let partition = balanced_partition(&[8, 7, 6, 5, 4]).unwrap();
+/// assert_eq!(partition.sums, vec![15, 15]);
+///
+/// let partition = balanced_partition(&[10, 3, 2]).unwrap();
+/// assert_eq!(partition.parts, vec![vec![1, 2], vec![0]]);
+/// assert_eq!(partition.difference(), 5);
+/// ```
+pub fn balanced_partition(numbers: &[u64]) -> Result<Partition, SumOverflowError> {
+    let total = total_sum(numbers)?;
+    // The subset with the largest sum not exceeding half of the total
+    let first_part = closest_subset_sum_vec(numbers, total / 2).indices;
+
+    let mut in_first_part = vec![false; numbers.len()];
+    for &index in &first_part {
+        in_first_part[index] = true;
+    }
+    let second_part = (0..numbers.len())
+        .filter(|&index| in_first_part[index].not())
+        .collect();
+    Ok(Partition::new(numbers, vec![first_part, second_part]))
+}
+
+/// Error returned by the functions of this module if the sum of the numbers does not fit into an u64.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SumOverflowError;
+
+impl fmt::Display for SumOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the sum of the numbers does not fit into an u64")
+    }
+}
+
+impl std::error::Error for SumOverflowError {}
+
+/// Returns the sum of `numbers`, or a [SumOverflowError] if it does not fit into an u64.
+fn total_sum(numbers: &[u64]) -> Result<u64, SumOverflowError> {
+    numbers
+        .iter()
+        .try_fold(0u64, |total, &number| total.checked_add(number))
+        .ok_or(SumOverflowError)
+}
+
+/// Splits `numbers` into `k` parts with the
+/// [Karmarkar-Karp largest differencing method](https://en.wikipedia.org/wiki/Largest_differencing_method). The result
+/// may not be optimal, but is usually good even for many numbers.
+///
+/// Each number starts as a partition of its own, with the number in one part and `k - 1` empty parts. The two
+/// partitions with the largest difference between their largest and smallest sum are repeatedly combined, joining the
+/// largest part of one with the smallest part of the other, until one partition is left.
+///
+/// Returns a [SumOverflowError] if the total sum does not fit into an u64. Panics if `k` is 0.
+///
+/// ```
+/// use aud2::partition::karmarkar_karp;
+///
+/// let partition = karmarkar_karp(&[8, 7, 6, 5, 4], 2).unwrap();
+/// assert_eq!(partition.sums, vec![16, 14]);
+/// ```
+pub fn karmarkar_karp(numbers: &[u64], k: usize) -> Result<Partition, SumOverflowError> {
+    assert!(k > 0, "k must be at least 1");
+    // Every sum of a part is at most the total, so no sum overflows below
+    total_sum(numbers)?;
+    if numbers.is_empty() {
+        return Ok(Partition::new(numbers, vec![Vec::new(); k]));
+    }
+    // Partitions to combine. The heap refers to them by their index and takes the one with the largest range first.
+    let mut subpartitions: Vec<Option<Subpartition>> = numbers
+        .iter()
+        .enumerate()
+        .map(|(index, &number)| Some(Subpartition::single(k, index, number)))
+        .collect();
+    let mut heap: BinaryHeap<(u64, Reverse<usize>)> = subpartitions
+        .iter()
+        .enumerate()
+        .map(|(i, subpartition)| (subpartition.as_ref().unwrap().range(), Reverse(i)))
+        .collect();
+
+    while heap.len() > 1 {
+        let (_, Reverse(first)) = heap.pop().unwrap();
+        let (_, Reverse(second)) = heap.pop().unwrap();
+        let first = subpartitions[first].take().unwrap();
+        let second = subpartitions[second].take().unwrap();
+        let combined = first.combine(&second, &differencing_permutation(k));
+        heap.push((combined.range(), Reverse(subpartitions.len())));
+        subpartitions.push(Some(combined));
+    }
+    let (_, Reverse(last)) = heap.pop().unwrap();
+    let partition = subpartitions[last].take().unwrap().into_partition(numbers);
+    log::info!("Karmarkar-Karp difference: {}", partition.difference());
+    Ok(partition)
+}
+
+/// The largest number of parts supported by [complete_karmarkar_karp]. A node of its search tree has up to
+/// 10! = 3628800 children.
+pub const MAX_COMPLETE_PARTS: usize = 10;
+
+/// The result of [complete_karmarkar_karp].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PartitionSearchResult {
+    /// The best partition found. If the search was stopped early, this is the best partition known so far.
+    pub partition: Partition,
+    /// Whether the search ran until the end, so that `partition` is optimal.
+    pub status: SolveStatus,
+    /// The number of explored nodes of the search tree.
+    pub explored_nodes: u64,
+}
+
+/// Splits `numbers` into `k` parts whose largest and smallest sum differ as little as possible, with Korf's
+/// [complete Karmarkar-Karp algorithm](https://doi.org/10.1016/S0004-3702(98)00086-1).
+///
+/// It is an anytime algorithm: It starts with the result of [karmarkar_karp] and searches all ways to combine two
+/// partitions in [karmarkar_karp], the differencing one first. Branches that cannot improve the best partition are
+/// pruned. The search can be stopped via [SolveOptions], e.g. by a deadline, and then returns the best partition found
+/// so far.
+///
+/// Each node of the search tree has up to k! children, so `k` is limited to [MAX_COMPLETE_PARTS]. Returns a
+/// [SumOverflowError] if the total sum does not fit into an u64. Panics if `k == 0` or `k > MAX_COMPLETE_PARTS`.
+///
+/// ```
+/// use aud2::knapsack::{SolveOptions, SolveStatus};
+/// use aud2::partition::complete_karmarkar_karp;
+///
+/// let result = complete_karmarkar_karp(&[8, 7, 6, 5, 4], 2, &SolveOptions::default()).unwrap();
+/// assert_eq!(result.partition.sums, vec![15, 15]);
+/// assert_eq!(result.status, SolveStatus::Completed);
+/// ```
+pub fn complete_karmarkar_karp(
+    numbers: &[u64],
+    k: usize,
+    options: &SolveOptions,
+) -> Result<PartitionSearchResult, SumOverflowError> {
+    assert!(
+        k <= MAX_COMPLETE_PARTS,
+        "k must be at most {MAX_COMPLETE_PARTS}"
+    );
+    let best = karmarkar_karp(numbers, k)?;
+    // Every sum of a part and the ranges of disjoint partitions are at most the total, so they do not overflow
+    let total = total_sum(numbers)?;
+    let mut context = SearchContext {
+        k,
+        // The difference is at least 1 if the total can not be split evenly
+        perfect_difference: u64::from(total % k as u64 != 0),
+        best_difference: best.difference(),
+        best: None,
+        options,
+        status: SolveStatus::Completed,
+        explored_nodes: 0,
+    };
+    let subpartitions = numbers
+        .iter()
+        .enumerate()
+        .map(|(index, &number)| Subpartition::single(k, index, number))
+        .collect();
+    search(subpartitions, &mut context);
+    log::info!(
+        "Complete Karmarkar-Karp difference: {} after {} nodes",
+        context.best_difference,
+        context.explored_nodes
+    );
+    Ok(PartitionSearchResult {
+        partition: context
+            .best
+            .map_or(best, |subpartition| subpartition.into_partition(numbers)),
+        status: context.status,
+        explored_nodes: context.explored_nodes,
+    })
+}
+
+/// State of [complete_karmarkar_karp] shared by all nodes of the search tree.
+struct SearchContext<'o, 'p> {
+    k: usize,
+    /// The smallest possible difference. The search stops once it is reached.
+    perfect_difference: u64,
+    /// Difference of the best partition found so far.
+    best_difference: u64,
+    /// The best partition found so far, if better than the one of [karmarkar_karp].
+    best: Option<Subpartition>,
+    options: &'o SolveOptions<'p>,
+    status: SolveStatus,
+    explored_nodes: u64,
+}
+
+/// Explores the node of [complete_karmarkar_karp] where `subpartitions` are left to combine.
+fn search(mut subpartitions: Vec<Subpartition>, context: &mut SearchContext) {
+    if context.should_stop() {
+        return;
+    }
+    context.explored_nodes += 1;
+    context
+        .options
+        .report(Progress::ExploredNodes(context.explored_nodes));
+
+    if subpartitions.len() == 1 {
+        let subpartition = subpartitions.pop().unwrap();
+        if subpartition.range() < context.best_difference {
+            log::debug!("Found partition with difference {}", subpartition.range());
+            context.best_difference = subpartition.range();
+            context.best = Some(subpartition);
+        }
+        return;
+    }
+
+    subpartitions.sort_by_key(|subpartition| Reverse(subpartition.range()));
+    // The other partitions can reduce the range of the first one at most by their own ranges
+    let others_range: u64 = subpartitions[1..].iter().map(Subpartition::range).sum();
+    let lower_bound = subpartitions[0].range().saturating_sub(others_range);
+    if lower_bound.max(context.perfect_difference) >= context.best_difference {
+        return;
+    }
+
+    let first = subpartitions.remove(0);
+    let second = subpartitions.remove(0);
+    // Combine the two partitions in all different ways. The permutations are generated one at a time, as there are k!
+    // of them, starting with the differencing one.
+    for permutation in (0..context.k).rev().permutations(context.k) {
+        if context.should_stop() {
+            return;
+        }
+        if is_distinct_combination(&first, &second, &permutation).not() {
+            continue;
+        }
+        let mut child_subpartitions = subpartitions.clone();
+        child_subpartitions.push(first.combine(&second, &permutation));
+        search(child_subpartitions, context);
+    }
+}
+
+impl SearchContext<'_, '_> {
+    /// Whether the search should be stopped, because it was stopped via the [SolveOptions] or the best partition can
+    /// not be improved anymore.
+    fn should_stop(&mut self) -> bool {
+        if self.status == SolveStatus::Completed {
+            if let Some(status) = self.options.stop_status() {
+                self.status = status;
+            }
+        }
+        self.status != SolveStatus::Completed || self.best_difference == self.perfect_difference
+    }
+}
+
+/// Whether combining `first` and `second` with `permutation` is the first of the permutations that lead to the same
+/// parts. Swapping parts with equal sums in either partition does not change the result, so only the permutation that
+/// is decreasing on parts with equal sums of `first` and whose inverse is decreasing on parts with equal sums of
+/// `second` is used.
+fn is_distinct_combination(
+    first: &Subpartition,
+    second: &Subpartition,
+    permutation: &[usize],
+) -> bool {
+    let mut positions = vec![0; permutation.len()];
+    for (i, &j) in permutation.iter().enumerate() {
+        positions[j] = i;
+    }
+    (1..permutation.len()).all(|i| {
+        (first.parts[i - 1].0 != first.parts[i].0 || permutation[i - 1] > permutation[i])
+            && (second.parts[i - 1].0 != second.parts[i].0 || positions[i - 1] > positions[i])
+    })
+}
+
+/// Returns the permutation used by [karmarkar_karp] to combine two partitions, which joins the largest part of one
+/// with the smallest part of the other and so on.
+fn differencing_permutation(k: usize) -> Vec<usize> {
+    (0..k).rev().collect()
+}
+
+/// A partition of some of the numbers into parts, used by [karmarkar_karp] and [complete_karmarkar_karp]. The parts
+/// are sorted by descending sum.
+#[derive(Debug, Clone)]
+struct Subpartition {
+    /// The sum and the indices of the numbers of each part.
+    parts: Vec<(u64, Vec<usize>)>,
+}
+
+impl Subpartition {
+    /// Creates the partition into `k` parts, one containing the number at `index` and the others empty.
+    fn single(k: usize, index: usize, number: u64) -> Self {
+        let mut parts = vec![(0, Vec::new()); k];
+        parts[0] = (number, vec![index]);
+        Subpartition { parts }
+    }
+
+    /// Difference between the largest and the smallest sum of a part.
+    fn range(&self) -> u64 {
+        self.parts[0].0 - self.parts[self.parts.len() - 1].0
+    }
+
+    /// Joins the `i`-th part of `self` with the `permutation[i]`-th part of `other`.
+    fn combine(&self, other: &Self, permutation: &[usize]) -> Self {
+        let mut parts: Vec<(u64, Vec<usize>)> = self
+            .parts
+            .iter()
+            .zip(permutation)
+            .map(|((sum, indices), &j)| {
+                let (other_sum, other_indices) = &other.parts[j];
+                (sum + other_sum, [&indices[..], &other_indices[..]].concat())
+            })
+            .collect();
+        parts.sort_by_key(|(sum, _)| Reverse(*sum));
+        Subpartition { parts }
+    }
+
+    fn into_partition(self, numbers: &[u64]) -> Partition {
+        Partition::new(
+            numbers,
+            self.parts.into_iter().map(|(_, indices)| indices).collect(),
+        )
+    }
+}
@@ -0,0 +1,202 @@
+//! Python bindings via [PyO3](https://pyo3.rs). Only available with the `python` feature, build the extension module
+//! with [maturin](https://www.maturin.rs), e.g. `maturin develop`.
+//!
+//! Items are passed as list of dicts `{"id": 1, "profit": 3, "weight": 20}` and results are returned as dicts with
+//! the same keys as the json output of the command line program.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::knapsack::{self, Item, Solution};
+use crate::subset_sum;
+
+/// Solves the maximum knapsack with dynamic programming.
+///
+/// Returns a dict with the chosen `items`, their total `profit` and `weight`, and the `weight_limit`.
+#[pyfunction]
+fn dynamic_programming<'py>(
+    py: Python<'py>,
+    items: Vec<Bound<'py, PyAny>>,
+    weight_limit: u64,
+) -> PyResult<Bound<'py, PyDict>> {
+    let items = extract_items(&items)?;
+    let solution = py.allow_threads(|| {
+        Solution::new(
+            knapsack::dynamic_programming(&items, weight_limit),
+            weight_limit,
+        )
+    });
+    solution_to_dict(py, &solution)
+}
+
+/// Solves the maximum knapsack with branch and bound. Returns a dict like `dynamic_programming`.
+#[pyfunction]
+fn branch_and_bound<'py>(
+    py: Python<'py>,
+    items: Vec<Bound<'py, PyAny>>,
+    weight_limit: u64,
+) -> PyResult<Bound<'py, PyDict>> {
+    let items = extract_items(&items)?;
+    let solution = py.allow_threads(|| {
+        Solution::new(
+            knapsack::branch_and_bound(&items, weight_limit),
+            weight_limit,
+        )
+    });
+    solution_to_dict(py, &solution)
+}
+
+/// Approximates the maximum knapsack with integer greedy. Returns a dict like `dynamic_programming`.
+#[pyfunction]
+fn integer_greedy<'py>(
+    py: Python<'py>,
+    items: Vec<Bound<'py, PyAny>>,
+    weight_limit: u64,
+) -> PyResult<Bound<'py, PyDict>> {
+    let items = extract_items(&items)?;
+    let solution = py.allow_threads(|| {
+        Solution::new(knapsack::integer_greedy(&items, weight_limit), weight_limit)
+    });
+    solution_to_dict(py, &solution)
+}
+
+/// Approximates the maximum knapsack with greedy_k, which tries all combinations of `k` fixed items. Returns a dict
+/// like `dynamic_programming`.
+#[pyfunction]
+fn greedy_k<'py>(
+    py: Python<'py>,
+    items: Vec<Bound<'py, PyAny>>,
+    weight_limit: u64,
+    k: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let items = extract_items(&items)?;
+    let solution = py
+        .allow_threads(|| Solution::new(knapsack::greedy_k(&items, weight_limit, k), weight_limit));
+    solution_to_dict(py, &solution)
+}
+
+/// Solves the fractional knapsack with the fractional greedy algorithm.
+///
+/// Returns a dict with the packed `items`, each a dict with the `item` and its `take_ratio` as `fractions.Fraction`,
+/// and the total `profit` as `fractions.Fraction`.
+#[pyfunction]
+fn fractional_greedy<'py>(
+    py: Python<'py>,
+    items: Vec<Bound<'py, PyAny>>,
+    weight_limit: u64,
+) -> PyResult<Bound<'py, PyDict>> {
+    let items = extract_items(&items)?;
+    let packed_items = knapsack::fractional_greedy(&items, weight_limit);
+    let python_fraction = py.import("fractions")?.getattr("Fraction")?;
+    let to_python_fraction = |fraction: fraction::Fraction| {
+        python_fraction.call1((
+            *fraction.numer().unwrap_or(&0),
+            *fraction.denom().unwrap_or(&1),
+        ))
+    };
+
+    let result = PyDict::new(py);
+    let python_items = packed_items
+        .iter()
+        .map(|packed_item| {
+            let python_item = PyDict::new(py);
+            python_item.set_item("item", item_to_dict(py, packed_item.item)?)?;
+            python_item.set_item("take_ratio", to_python_fraction(packed_item.take_ratio)?)?;
+            Ok(python_item)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    result.set_item("items", python_items)?;
+    let profit = packed_items
+        .iter()
+        .map(knapsack::PartialPackedItem::effective_profit)
+        .sum();
+    result.set_item("profit", to_python_fraction(profit)?)?;
+    Ok(result)
+}
+
+/// Decides whether a subset of `numbers` sums up to `sum`, using a set of reachable sums.
+///
+/// Returns a dict with the `numbers`, the `sum`, whether it is `reachable` and the `witness`, i.e. the indices of the
+/// numbers summing up to `sum` or `None`.
+#[pyfunction]
+fn subset_sum_set(py: Python<'_>, numbers: Vec<u64>, sum: u64) -> PyResult<Bound<'_, PyDict>> {
+    let witness = py.allow_threads(|| subset_sum::subset_sum_set_witness(&numbers, sum));
+    subset_sum_to_dict(py, numbers, sum, witness)
+}
+
+/// Decides whether a subset of `numbers` sums up to `sum`, using a table of reachable sums. Returns a dict like
+/// `subset_sum_set`.
+#[pyfunction]
+fn subset_sum_vec(py: Python<'_>, numbers: Vec<u64>, sum: u64) -> PyResult<Bound<'_, PyDict>> {
+    let witness = py.allow_threads(|| subset_sum::subset_sum_vec_witness(&numbers, sum));
+    subset_sum_to_dict(py, numbers, sum, witness)
+}
+
+/// Algorithms from "Algorithms and Data Structures 2" implemented in Rust.
+#[pymodule]
+#[pyo3(name = "aud2")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(fractional_greedy, module)?)?;
+    module.add_function(wrap_pyfunction!(dynamic_programming, module)?)?;
+    module.add_function(wrap_pyfunction!(integer_greedy, module)?)?;
+    module.add_function(wrap_pyfunction!(greedy_k, module)?)?;
+    module.add_function(wrap_pyfunction!(branch_and_bound, module)?)?;
+    module.add_function(wrap_pyfunction!(subset_sum_set, module)?)?;
+    module.add_function(wrap_pyfunction!(subset_sum_vec, module)?)?;
+    Ok(())
+}
+
+// Helper functions
+
+/// Reads the keys `id`, `profit` and `weight` of each dict in `items`.
+fn extract_items(items: &[Bound<'_, PyAny>]) -> PyResult<Vec<Item>> {
+    items
+        .iter()
+        .map(|item| {
+            Ok(Item {
+                id: item.get_item("id")?.extract()?,
+                profit: item.get_item("profit")?.extract()?,
+                weight: item.get_item("weight")?.extract()?,
+            })
+        })
+        .collect()
+}
+
+/// Converts `item` to a dict with the keys `id`, `profit` and `weight`.
+fn item_to_dict<'py>(py: Python<'py>, item: &Item) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", item.id)?;
+    dict.set_item("profit", item.profit)?;
+    dict.set_item("weight", item.weight)?;
+    Ok(dict)
+}
+
+/// Converts `solution` to a dict with the keys `items`, `profit`, `weight` and `weight_limit`.
+fn solution_to_dict<'py>(py: Python<'py>, solution: &Solution) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    let items = solution
+        .items
+        .iter()
+        .map(|item| item_to_dict(py, item))
+        .collect::<PyResult<Vec<_>>>()?;
+    dict.set_item("items", items)?;
+    dict.set_item("profit", solution.profit)?;
+    dict.set_item("weight", solution.weight)?;
+    dict.set_item("weight_limit", solution.weight_limit)?;
+    Ok(dict)
+}
+
+/// Converts the result of a subset sum solver to a dict with the keys `numbers`, `sum`, `reachable` and `witness`.
+fn subset_sum_to_dict(
+    py: Python<'_>,
+    numbers: Vec<u64>,
+    sum: u64,
+    witness: Option<Vec<usize>>,
+) -> PyResult<Bound<'_, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("numbers", numbers)?;
+    dict.set_item("sum", sum)?;
+    dict.set_item("reachable", witness.is_some())?;
+    dict.set_item("witness", witness)?;
+    Ok(dict)
+}
@@ -3,9 +3,12 @@
 //! From Wikipedia: "In its most general formulation, there is a multiset S of integers and a target-sum T, and the
 //! question is to decide whether any subset of the integers sum to precisely T."
 
+use itertools::Itertools;
 use log::log_enabled;
-use std::collections::HashSet;
-use std::ops::Not;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::num::NonZeroU128;
+use std::ops::{Bound, Not, RangeBounds, SubAssign};
 
 /// Solves the [subset sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem) via
 /// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming).
@@ -51,8 +54,69 @@ pub fn subset_sum_set(numbers: &[u64], limit: u64) -> bool {
     row.contains(&limit)
 }
 
+/// Like [subset_sum_set], but returns the indices of numbers summing up to `limit` in ascending order, or `None` if
+/// `limit` is not reachable.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_set_witness;
+///
+/// assert_eq!(subset_sum_set_witness(&[3, 5, 9], 14), Some(vec![1, 2]));
+/// assert_eq!(subset_sum_set_witness(&[3, 5, 9], 13), None);
+/// ```
+pub fn subset_sum_set_witness(numbers: &[u64], limit: u64) -> Option<Vec<usize>> {
+    let row = first_index_map(numbers, limit);
+    row.contains_key(&limit)
+        .then(|| backtrack(numbers, limit, |sum| row[&sum]))
+}
+
+/// Computes the set of [subset_sum_set_witness]. It maps each reachable sum from 0 to `limit` to the index of the
+/// number with which it was reached first.
+fn first_index_map(numbers: &[u64], limit: u64) -> HashMap<u64, usize> {
+    // Contains the sums that are producible by using (some of) the first i numbers. Each sum is mapped to the index of
+    // the number with which it was reached first, which is needed to backtrack a witness.
+    let mut row: HashMap<u64, usize> = HashMap::new();
+    // The number 0 can be produced with the first 0 numbers. It is the start of each witness, so it has no index.
+    row.insert(0, usize::MAX);
+
+    /// Helper function for logging
+    fn log_row(row: &HashMap<u64, usize>, i: usize) {
+        // Log this row as sorted sums. Only do this computation when logging is enabled for this level.
+        let log_level = log::Level::Debug;
+        if log_enabled!(log_level) {
+            let mut sorted_sums = Vec::from_iter(row.keys());
+            sorted_sums.sort();
+            log::log!(
+                log_level,
+                "i={}: reachable {} sums: {:?}",
+                i,
+                sorted_sums.len(),
+                sorted_sums
+            );
+        }
+    }
+    log_row(&row, 0);
+
+    // Examine which numbers are producible by using a new number from the number list.
+    for (i, new_number) in numbers.iter().enumerate() {
+        let last_row: Vec<u64> = row.keys().copied().collect();
+        // All previously reachable numbers are still reachable
+        // In addition, each old number + new_number is now also reachable
+        for already_reachable_sum in last_row {
+            let new_reachable_sum = already_reachable_sum + new_number;
+            if new_reachable_sum > limit {
+                continue;
+            }
+            row.entry(new_reachable_sum).or_insert(i);
+        }
+        // The first row is actually before this loop
+        log_row(&row, i + 1);
+    }
+    row
+}
+
 /// Solves the [subset sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem) via
 /// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming).
+#[allow(clippy::unnecessary_cast)]
 pub fn subset_sum_vec(numbers: &[u64], limit: u64) -> bool {
     // Convert u64 to usize to provide a consistent API for subset sum implementations
     let limit = limit as usize;
@@ -102,3 +166,694 @@ pub fn subset_sum_vec(numbers: &[u64], limit: u64) -> bool {
     }
     row[limit]
 }
+
+/// Like [subset_sum_vec], but returns the indices of numbers summing up to `limit` in ascending order, or `None` if
+/// `limit` is not reachable.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_vec_witness;
+///
+/// assert_eq!(subset_sum_vec_witness(&[3, 5, 9], 14), Some(vec![1, 2]));
+/// assert_eq!(subset_sum_vec_witness(&[3, 5, 9], 13), None);
+/// ```
+pub fn subset_sum_vec_witness(numbers: &[u64], limit: u64) -> Option<Vec<usize>> {
+    let row = first_index_table(numbers, limit);
+    table_witness(numbers, &row, limit)
+}
+
+/// Computes the table of [subset_sum_vec_witness]. It contains for each sum from 0 to `limit` the index of the number
+/// with which it was reached first, or `None` if it is not reachable.
+fn first_index_table(numbers: &[u64], limit: u64) -> Vec<Option<usize>> {
+    // Convert u64 to usize to provide a consistent API for subset sum implementations
+    let limit_index = limit as usize;
+    // Contains for each sum whether it can be produced by summing (some of) the first i numbers. A producible sum
+    // holds the index of the number with which it was reached first, which is needed to backtrack a witness.
+    let mut row: Vec<Option<usize>> = vec![None; limit_index + 1];
+    // The number 0 can be produced with the first 0 numbers. It is the start of each witness, so it has no index.
+    row[0] = Some(usize::MAX);
+
+    /// Helper function for logging
+    fn log_row(row: &[Option<usize>], i: usize) {
+        let log_level = log::Level::Debug;
+        if log_enabled!(log_level) {
+            // Extract sums that are producible
+            let row_sums: Vec<usize> = row
+                .iter()
+                .enumerate()
+                .filter(|(_sum, reachable)| reachable.is_some())
+                .map(|(sum, _reachable)| sum)
+                .collect();
+            log::log!(
+                log_level,
+                "i={} reachable {} sums: {:?}",
+                i,
+                row_sums.len(),
+                row_sums
+            );
+        }
+    }
+    log_row(&row, 0);
+
+    // Examine which numbers are producible by using a new number from the number list.
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        let last_row = row.clone();
+        // All previously reachable numbers are still reachable
+        // In addition, each old number + new_number is now also reachable
+        for (sum, reachable) in last_row.iter().enumerate() {
+            if reachable.is_none() {
+                // Sum previously not producible, so no new sum here
+                continue;
+            }
+            // New sum producible. Only set it if not out of bounds (would result in .get_mut() return None) and keep
+            // the index of a sum that was already producible before.
+            if let Some(new_sum) = row.get_mut(sum + (new_number as usize)) {
+                new_sum.get_or_insert(i);
+            }
+        }
+        log_row(&row, i + 1);
+    }
+    row
+}
+
+/// Returns the indices of numbers summing up to `sum` in ascending order, or `None` if `sum` is not reachable. `table`
+/// is computed by [first_index_table] with a limit of at least `sum`.
+fn table_witness(numbers: &[u64], table: &[Option<usize>], sum: u64) -> Option<Vec<usize>> {
+    table[sum as usize].map(|_| backtrack(numbers, sum, |sum| table[sum as usize].unwrap()))
+}
+
+/// Solves the [subset sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem) via
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming) on a bitset.
+///
+/// Like [subset_sum_vec], but the reachable sums are packed into u64 words, which needs 64 times less memory. Adding a
+/// new number is a single shift-or over the words: `reachable |= reachable << new_number`.
+pub fn subset_sum_bitset(numbers: &[u64], limit: u64) -> bool {
+    let mut row = Bitset::new(limit);
+    log_bitset(&row, 0);
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        row.shift_or(new_number);
+        log_bitset(&row, i + 1);
+    }
+    row.contains(limit)
+}
+
+/// Like [subset_sum_bitset], but returns the indices of numbers summing up to `limit` in ascending order, or `None` if
+/// `limit` is not reachable.
+///
+/// All rows are kept to backtrack the witness, so this needs `numbers.len() + 1` times the memory of
+/// [subset_sum_bitset], i.e. `(numbers.len() + 1) * (limit / 64 + 1)` words. For more than 8 numbers, that is more than
+/// the one byte per sum of [subset_sum_vec]. Use [subset_sum_bitset] if only reachability is needed.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_bitset_witness;
+///
+/// assert_eq!(subset_sum_bitset_witness(&[3, 5, 9], 14), Some(vec![1, 2]));
+/// assert_eq!(subset_sum_bitset_witness(&[3, 5, 9], 13), None);
+/// ```
+pub fn subset_sum_bitset_witness(numbers: &[u64], limit: u64) -> Option<Vec<usize>> {
+    // rows[i] contains the sums that are producible by using (some of) the first i numbers.
+    let mut rows = vec![Bitset::new(limit)];
+    log_bitset(&rows[0], 0);
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        let mut row = rows[i].clone();
+        row.shift_or(new_number);
+        log_bitset(&row, i + 1);
+        rows.push(row);
+    }
+    if rows[numbers.len()].contains(limit).not() {
+        return None;
+    }
+
+    // A sum that is not producible without the number i must use it
+    let mut sum = limit;
+    let mut witness = Vec::new();
+    for i in (0..numbers.len()).rev() {
+        if rows[i].contains(sum).not() {
+            witness.push(i);
+            sum -= numbers[i];
+        }
+    }
+    witness.reverse();
+    Some(witness)
+}
+
+/// Solves the [subset sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem) with possibly negative numbers
+/// and `target` via [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming) on a set of reachable
+/// sums. Returns the indices of numbers summing up to `target` in ascending order, or `None` if `target` is not
+/// reachable.
+///
+/// Like [subset_sum_set_witness], but a sum greater than `target` may still reach it by adding negative numbers. So a
+/// sum is only dropped if `target` is out of reach with the remaining numbers, i.e. even adding all remaining negative
+/// or all remaining positive numbers does not reach it.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_signed_set;
+///
+/// assert_eq!(subset_sum_signed_set(&[5, -7, 3, 9], -2), Some(vec![0, 1]));
+/// assert_eq!(subset_sum_signed_set(&[5, -7, 3, 9], -3), None);
+/// ```
+pub fn subset_sum_signed_set(numbers: &[i64], target: i64) -> Option<Vec<usize>> {
+    let (remaining_negative, remaining_positive) = remaining_sums(numbers);
+    // Whether target is reachable from sum with the numbers from index i on
+    let in_reach = |sum: i64, i: usize| {
+        (remaining_negative[i]..=remaining_positive[i]).contains(&(target - sum))
+    };
+
+    // Contains the sums that are producible by using (some of) the first i numbers and can still reach the target.
+    let mut row: HashSet<i64> = HashSet::new();
+    // Maps each sum that was ever producible to the index of the number with which it was reached first, which is
+    // needed to backtrack a witness. Unlike row, sums are never removed, so the indices never change.
+    let mut first_indices: HashMap<i64, usize> = HashMap::new();
+    // The number 0 can be produced with the first 0 numbers. It is the start of each witness, so it has no index.
+    first_indices.insert(0, usize::MAX);
+    if in_reach(0, 0) {
+        row.insert(0);
+    }
+
+    /// Helper function for logging
+    fn log_row(row: &HashSet<i64>, i: usize) {
+        // Log this row as sorted sums. Only do this computation when logging is enabled for this level.
+        let log_level = log::Level::Debug;
+        if log_enabled!(log_level) {
+            let mut sorted_sums = Vec::from_iter(row);
+            sorted_sums.sort();
+            log::log!(
+                log_level,
+                "i={}: reachable {} sums: {:?}",
+                i,
+                sorted_sums.len(),
+                sorted_sums
+            );
+        }
+    }
+    log_row(&row, 0);
+
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        let last_row: Vec<i64> = row.iter().copied().collect();
+        // Each old sum + new_number is now also reachable
+        for already_reachable_sum in last_row {
+            let new_reachable_sum = already_reachable_sum + new_number;
+            row.insert(new_reachable_sum);
+            first_indices.entry(new_reachable_sum).or_insert(i);
+        }
+        // Drop all sums, old and new, from which target is out of reach with the numbers after this one
+        row.retain(|&sum| in_reach(sum, i + 1));
+        log_row(&row, i + 1);
+    }
+    row.contains(&target)
+        .then(|| backtrack(numbers, target, |sum| first_indices[&sum]))
+}
+
+/// Solves the [subset sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem) with possibly negative numbers
+/// and `target` via [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming) on a table of all sums.
+/// Returns the indices of numbers summing up to `target` in ascending order, or `None` if `target` is not reachable.
+///
+/// Like [subset_sum_vec_witness], but the table ranges from the sum of all negative numbers to the sum of all positive
+/// numbers. The sum `s` is stored at index `s - (sum of all negative numbers)`.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_signed_vec;
+///
+/// assert_eq!(subset_sum_signed_vec(&[5, -7, 3, 9], -2), Some(vec![0, 1]));
+/// assert_eq!(subset_sum_signed_vec(&[5, -7, 3, 9], -3), None);
+/// ```
+pub fn subset_sum_signed_vec(numbers: &[i64], target: i64) -> Option<Vec<usize>> {
+    let (remaining_negative, remaining_positive) = remaining_sums(numbers);
+    let (min_sum, max_sum) = (remaining_negative[0], remaining_positive[0]);
+    if (min_sum..=max_sum).contains(&target).not() {
+        return None;
+    }
+    // Index of a sum in the table
+    let index = |sum: i64| (sum - min_sum) as usize;
+
+    // Contains for each sum whether it can be produced by summing (some of) the first i numbers. A producible sum
+    // holds the index of the number with which it was reached first, which is needed to backtrack a witness.
+    let mut row: Vec<Option<usize>> = vec![None; index(max_sum) + 1];
+    // The number 0 can be produced with the first 0 numbers. It is the start of each witness, so it has no index.
+    row[index(0)] = Some(usize::MAX);
+
+    /// Helper function for logging
+    fn log_row(row: &[Option<usize>], min_sum: i64, i: usize) {
+        let log_level = log::Level::Debug;
+        if log_enabled!(log_level) {
+            // Extract sums that are producible
+            let row_sums: Vec<i64> = row
+                .iter()
+                .enumerate()
+                .filter(|(_index, reachable)| reachable.is_some())
+                .map(|(index, _reachable)| index as i64 + min_sum)
+                .collect();
+            log::log!(
+                log_level,
+                "i={} reachable {} sums: {:?}",
+                i,
+                row_sums.len(),
+                row_sums
+            );
+        }
+    }
+    log_row(&row, min_sum, 0);
+
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        let last_row = row.clone();
+        // Each old sum + new_number is now also reachable. It is always inside the table, because the table contains
+        // all sums of any subset.
+        for (sum_index, reachable) in last_row.iter().enumerate() {
+            if reachable.is_some() {
+                let new_sum = sum_index as i64 + min_sum + new_number;
+                row[index(new_sum)].get_or_insert(i);
+            }
+        }
+        log_row(&row, min_sum, i + 1);
+    }
+    row[index(target)].map(|_| backtrack(numbers, target, |sum| row[index(sum)].unwrap()))
+}
+
+/// Solves the [subset sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem) with a constraint on how many
+/// numbers may be used, via [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming). Returns the
+/// indices of numbers summing up to `target` in ascending order, or `None` if `target` is not reachable with a number
+/// of numbers in `counts`. If there are several subsets, one with the fewest numbers is returned.
+///
+/// Like [subset_sum_vec_witness], but the table has an additional dimension for the number of used numbers. So it needs
+/// `O(k * target)` memory and `O(n * k * target)` time, where `k` is the largest allowed count.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_cardinality;
+///
+/// // 12 = 3 + 9 = 1 + 3 + 8
+/// let numbers = [1, 3, 8, 9];
+/// assert_eq!(subset_sum_cardinality(&numbers, 12, 3..=3), Some(vec![0, 1, 2]));
+/// assert_eq!(subset_sum_cardinality(&numbers, 12, ..=2), Some(vec![1, 3]));
+/// assert_eq!(subset_sum_cardinality(&numbers, 12, 4..), None);
+/// ```
+pub fn subset_sum_cardinality(
+    numbers: &[u64],
+    target: u64,
+    counts: impl RangeBounds<usize>,
+) -> Option<Vec<usize>> {
+    let min_count = match counts.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let max_count = match counts.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) => end.checked_sub(1)?,
+        Bound::Unbounded => numbers.len(),
+    }
+    .min(numbers.len());
+    if min_count > max_count {
+        return None;
+    }
+
+    // table[count][sum] holds the index of the number with which sum was first reached using count numbers, or None if
+    // it is not reachable with count of (some of) the first i numbers.
+    let mut table: Vec<Vec<Option<usize>>> = vec![vec![None; target as usize + 1]; max_count + 1];
+    // The number 0 can be produced with 0 numbers. It is the start of each witness, so it has no index.
+    table[0][0] = Some(usize::MAX);
+
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        if new_number > target {
+            continue;
+        }
+        // Iterate the counts in descending order, so that table[count - 1] does not contain new_number yet
+        for count in (1..=max_count).rev() {
+            let (fewer, current) = table.split_at_mut(count);
+            let (fewer, current) = (&fewer[count - 1], &mut current[0]);
+            for sum in new_number as usize..=target as usize {
+                if current[sum].is_none() && fewer[sum - new_number as usize].is_some() {
+                    current[sum] = Some(i);
+                }
+            }
+        }
+        log::debug!(
+            "i={} reachable (count, sum) pairs: {}",
+            i + 1,
+            table
+                .iter()
+                .flatten()
+                .filter(|entry| entry.is_some())
+                .count()
+        );
+    }
+
+    let mut count =
+        (min_count..=max_count).find(|&count| table[count][target as usize].is_some())?;
+    // Each step removes the number with which the sum was reached first, which was reachable with one number less
+    // before, like in [backtrack]
+    let mut sum = target;
+    let mut witness = Vec::new();
+    while count > 0 {
+        let index = table[count][sum as usize].unwrap();
+        witness.push(index);
+        sum -= numbers[index];
+        count -= 1;
+    }
+    witness.reverse();
+    Some(witness)
+}
+
+/// A subset of numbers, referred to by their indices.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Subset {
+    /// The indices of the numbers in the subset, in ascending order.
+    pub indices: Vec<usize>,
+    /// The sum of the numbers in the subset.
+    pub sum: u64,
+}
+
+/// Returns a subset of `numbers` with the largest sum not exceeding `target`, using a set of reachable sums like
+/// [subset_sum_set].
+///
+/// ```
+/// use aud2::subset_sum::closest_subset_sum_set;
+///
+/// let subset = closest_subset_sum_set(&[3, 5, 9], 13);
+/// assert_eq!((subset.sum, subset.indices), (12, vec![0, 2]));
+/// ```
+pub fn closest_subset_sum_set(numbers: &[u64], target: u64) -> Subset {
+    let row = first_index_map(numbers, target);
+    // The sum 0 is always reachable
+    let sum = *row.keys().max().unwrap();
+    Subset {
+        indices: backtrack(numbers, sum, |sum| row[&sum]),
+        sum,
+    }
+}
+
+/// Returns a subset of `numbers` with the largest sum not exceeding `target`, using a table of reachable sums like
+/// [subset_sum_vec].
+///
+/// ```
+/// use aud2::subset_sum::closest_subset_sum_vec;
+///
+/// let subset = closest_subset_sum_vec(&[3, 5, 9], 13);
+/// assert_eq!((subset.sum, subset.indices), (12, vec![0, 2]));
+/// ```
+pub fn closest_subset_sum_vec(numbers: &[u64], target: u64) -> Subset {
+    let table = first_index_table(numbers, target);
+    // The sum 0 is always reachable
+    let sum = (0..=target)
+        .rev()
+        .find(|&sum| table[sum as usize].is_some())
+        .unwrap();
+    Subset {
+        indices: table_witness(numbers, &table, sum).unwrap(),
+        sum,
+    }
+}
+
+/// Returns a subset of `numbers` whose sum is nearest to `target`, below or above it, using a set of reachable sums
+/// like [subset_sum_set]. If two sums are equally near, the one below `target` is chosen.
+///
+/// ```
+/// use aud2::subset_sum::nearest_subset_sum_set;
+///
+/// let subset = nearest_subset_sum_set(&[3, 5, 9], 11);
+/// assert_eq!((subset.sum, subset.indices), (12, vec![0, 2]));
+/// ```
+pub fn nearest_subset_sum_set(numbers: &[u64], target: u64) -> Subset {
+    let row = first_index_map(numbers, nearest_limit(numbers, target));
+    let sum = *row
+        .keys()
+        .min_by_key(|&&sum| (sum.abs_diff(target), sum))
+        .unwrap();
+    Subset {
+        indices: backtrack(numbers, sum, |sum| row[&sum]),
+        sum,
+    }
+}
+
+/// Returns a subset of `numbers` whose sum is nearest to `target`, below or above it, using a table of reachable sums
+/// like [subset_sum_vec]. If two sums are equally near, the one below `target` is chosen.
+///
+/// ```
+/// use aud2::subset_sum::nearest_subset_sum_vec;
+///
+/// let subset = nearest_subset_sum_vec(&[3, 5, 9], 11);
+/// assert_eq!((subset.sum, subset.indices), (12, vec![0, 2]));
+/// ```
+pub fn nearest_subset_sum_vec(numbers: &[u64], target: u64) -> Subset {
+    let table = first_index_table(numbers, nearest_limit(numbers, target));
+    let sum = (0..table.len() as u64)
+        .filter(|&sum| table[sum as usize].is_some())
+        .min_by_key(|&sum| (sum.abs_diff(target), sum))
+        .unwrap();
+    Subset {
+        indices: table_witness(numbers, &table, sum).unwrap(),
+        sum,
+    }
+}
+
+/// Approximates the largest subset sum not exceeding `target` with the
+/// [fully polynomial-time approximation scheme](https://en.wikipedia.org/wiki/Subset_sum_problem#Fully-polynomial_time_approximation_scheme)
+/// by trimming the list of reachable sums.
+///
+/// Returns a subset whose sum is at most `target` and at least `(1 - epsilon)` times the largest subset sum not
+/// exceeding `target`. Like [subset_sum_set], it computes the reachable sums number by number, but after each number
+/// it drops every sum that is within a factor of `1 + epsilon / (2n)` of a smaller kept sum. So the list contains
+/// only `O(n log(target) / epsilon)` sums, even if the numbers are large.
+///
+/// Panics if `epsilon` is not positive.
+///
+/// ```
+/// use aud2::subset_sum::approximate_subset_sum;
+///
+/// let subset = approximate_subset_sum(&[104, 102, 201, 101], 308, 0.4);
+/// assert!(subset.sum <= 308 && subset.sum as f64 >= 0.6 * 307.0);
+/// assert_eq!(subset.indices.iter().map(|&i| [104, 102, 201, 101][i]).sum::<u64>(), subset.sum);
+/// ```
+pub fn approximate_subset_sum(numbers: &[u64], target: u64, epsilon: f64) -> Subset {
+    assert!(epsilon > 0.0, "epsilon must be positive");
+    let delta = epsilon / (2 * numbers.len().max(1)) as f64;
+
+    // Each sum in the list refers to the node of the number added last. A node contains the index of this number and
+    // the node of the sum it was added to, which is needed to backtrack the subset.
+    let mut nodes: Vec<(usize, Option<usize>)> = Vec::new();
+    // The trimmed list of reachable sums in ascending order. The sum 0 is reached by the empty subset.
+    let mut list: Vec<(u64, Option<usize>)> = vec![(0, None)];
+
+    /// Helper function for logging
+    fn log_list(list: &[(u64, Option<usize>)], i: usize) {
+        let log_level = log::Level::Debug;
+        if log_enabled!(log_level) {
+            let sums: Vec<u64> = list.iter().map(|(sum, _node)| *sum).collect();
+            log::log!(
+                log_level,
+                "i={} trimmed list of {} sums: {:?}",
+                i,
+                sums.len(),
+                sums
+            );
+        }
+    }
+    log_list(&list, 0);
+
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        // Merge the old sums with the old sums + new_number, both in ascending order. Sums above target are dropped.
+        let mut extended = Vec::new();
+        for &(sum, node) in &list {
+            match sum.checked_add(new_number) {
+                Some(new_sum) if new_sum <= target => {
+                    nodes.push((i, node));
+                    extended.push((new_sum, Some(nodes.len() - 1)));
+                }
+                _ => break,
+            }
+        }
+        let merged = list
+            .into_iter()
+            .merge_by(extended, |(sum, _), (other_sum, _)| sum <= other_sum);
+
+        // Trim: Only keep a sum if it is not represented well by the last kept one
+        list = Vec::new();
+        for (sum, node) in merged {
+            match list.last() {
+                Some(&(last_sum, _)) if sum as f64 <= last_sum as f64 * (1.0 + delta) => {}
+                _ => list.push((sum, node)),
+            }
+        }
+        log_list(&list, i + 1);
+    }
+
+    let (sum, mut node) = *list.last().unwrap();
+    let mut indices = Vec::new();
+    while let Some(current) = node {
+        let (index, parent) = nodes[current];
+        indices.push(index);
+        node = parent;
+    }
+    indices.reverse();
+    Subset { indices, sum }
+}
+
+/// Counts for every sum from 0 to `limit` how many subsets of `numbers` add up to it, via
+/// [dynamic programming](https://en.wikipedia.org/wiki/Dynamic_programming). Subsets are distinguished by the indices
+/// of their numbers, so equal numbers at different positions form different subsets. If `modulus` is given, all counts
+/// are reduced modulo `modulus`, which avoids overflows for many numbers.
+///
+/// Returns a [CountOverflowError] if a count does not fit into an u128 and no `modulus` is given.
+///
+/// ```
+/// use aud2::subset_sum::subset_sum_count;
+///
+/// // 6 = 1 + 2 + 3 = 1 + 2 + 3 = 3 + 3, with two different 3's
+/// assert_eq!(subset_sum_count(&[1, 2, 3, 3], 6, None), Ok(vec![1, 1, 1, 3, 2, 2, 3]));
+/// ```
+pub fn subset_sum_count(
+    numbers: &[u64],
+    limit: u64,
+    modulus: Option<NonZeroU128>,
+) -> Result<Vec<u128>, CountOverflowError> {
+    // Contains for each sum the number of subsets of the first i numbers adding up to it.
+    let mut row: Vec<u128> = vec![0; limit as usize + 1];
+    // The number 0 can be produced with the first 0 numbers, namely by the empty subset.
+    row[0] = modulus.map_or(1, |modulus| 1 % modulus.get());
+
+    for (i, new_number) in numbers.iter().copied().enumerate() {
+        let new_number = new_number as usize;
+        // Each subset reaching sum - new_number is extended by new_number. Iterate the sums in descending order, so
+        // that row[sum - new_number] still contains the count without new_number.
+        for sum in (new_number..row.len()).rev() {
+            let extended = row[sum - new_number];
+            row[sum] = match modulus {
+                Some(modulus) => {
+                    // Both counts are smaller than modulus, so subtracting avoids an overflow of the addition
+                    let modulus = modulus.get();
+                    if row[sum] >= modulus - extended {
+                        row[sum] - (modulus - extended)
+                    } else {
+                        row[sum] + extended
+                    }
+                }
+                None => row[sum]
+                    .checked_add(extended)
+                    .ok_or(CountOverflowError { sum: sum as u64 })?,
+            };
+        }
+        log::debug!("i={} counts: {:?}", i + 1, row);
+    }
+    Ok(row)
+}
+
+/// Error returned by [subset_sum_count] if the number of subsets does not fit into an u128.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CountOverflowError {
+    /// The first sum whose count overflowed.
+    pub sum: u64,
+}
+
+impl fmt::Display for CountOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the number of subsets with sum {} does not fit into an u128, count modulo a number instead",
+            self.sum
+        )
+    }
+}
+
+impl std::error::Error for CountOverflowError {}
+
+// Helper Functions
+
+/// Returns the largest sum that has to be considered to find the sum nearest to `target`. Sums above twice the target
+/// are farther away than the always reachable sum 0, and no sum is above the total.
+fn nearest_limit(numbers: &[u64], target: u64) -> u64 {
+    let total = numbers
+        .iter()
+        .fold(0u64, |total, &number| total.saturating_add(number));
+    total.min(target.saturating_mul(2))
+}
+
+/// Returns for each index `i` the sum of all negative and the sum of all positive numbers from index `i` on, i.e. the
+/// range of sums that can be added with these numbers. Both vectors have `numbers.len() + 1` entries.
+fn remaining_sums(numbers: &[i64]) -> (Vec<i64>, Vec<i64>) {
+    let mut remaining_negative = vec![0; numbers.len() + 1];
+    let mut remaining_positive = vec![0; numbers.len() + 1];
+    for (i, &number) in numbers.iter().enumerate().rev() {
+        remaining_negative[i] = remaining_negative[i + 1] + number.min(0);
+        remaining_positive[i] = remaining_positive[i + 1] + number.max(0);
+    }
+    (remaining_negative, remaining_positive)
+}
+
+/// Reconstructs the indices of numbers summing up to `sum` in ascending order. `first_index(s)` returns the index of
+/// the number with which the reachable sum `s` was reached first. The remaining sum was then already reachable with the
+/// numbers before, so the indices strictly decrease while backtracking and no number is used twice.
+fn backtrack<T>(numbers: &[T], mut sum: T, first_index: impl Fn(T) -> usize) -> Vec<usize>
+where
+    T: Copy + Default + PartialEq + SubAssign,
+{
+    let mut witness = Vec::new();
+    // The sum 0 is reached by the empty subset
+    while sum != T::default() {
+        let index = first_index(sum);
+        witness.push(index);
+        sum -= numbers[index];
+    }
+    witness.reverse();
+    witness
+}
+
+/// The sums from 0 to a limit, packed into u64 words. Used by [subset_sum_bitset].
+#[derive(Debug, Clone)]
+struct Bitset {
+    words: Vec<u64>,
+    limit: u64,
+}
+
+impl Bitset {
+    /// Creates a bitset for the sums from 0 to `limit`, containing only 0.
+    fn new(limit: u64) -> Self {
+        let mut words = vec![0; limit as usize / 64 + 1];
+        words[0] = 1;
+        Bitset { words, limit }
+    }
+
+    fn contains(&self, sum: u64) -> bool {
+        sum <= self.limit && self.words[sum as usize / 64] >> (sum % 64) & 1 == 1
+    }
+
+    /// Adds `shift` to each contained sum and keeps the old sums, i.e. `self |= self << shift`.
+    fn shift_or(&mut self, shift: u64) {
+        if shift > self.limit {
+            // Only produces sums out of bounds
+            return;
+        }
+        let word_shift = shift as usize / 64;
+        let bit_shift = shift % 64;
+        // Walk from the highest to the lowest word, so each word read is not overwritten yet
+        for i in (word_shift..self.words.len()).rev() {
+            let mut shifted = self.words[i - word_shift] << bit_shift;
+            if bit_shift > 0 && i > word_shift {
+                shifted |= self.words[i - word_shift - 1] >> (64 - bit_shift);
+            }
+            self.words[i] |= shifted;
+        }
+        // Clear the sums above the limit in the last word
+        let used_bits = self.limit % 64 + 1;
+        if used_bits < 64 {
+            *self.words.last_mut().unwrap() &= (1 << used_bits) - 1;
+        }
+    }
+
+    /// Iterates over the contained sums in ascending order.
+    fn sums(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..=self.limit).filter(|&sum| self.contains(sum))
+    }
+}
+
+/// Helper function for logging the reachable sums of [subset_sum_bitset].
+fn log_bitset(row: &Bitset, i: usize) {
+    let log_level = log::Level::Debug;
+    if log_enabled!(log_level) {
+        let row_sums: Vec<u64> = row.sums().collect();
+        log::log!(
+            log_level,
+            "i={} reachable {} sums: {:?}",
+            i,
+            row_sums.len(),
+            row_sums
+        );
+    }
+}
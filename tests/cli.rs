@@ -0,0 +1,182 @@
+use std::fs;
+use std::ops::Not;
+use std::process::Command;
+
+#[test]
+fn test_export_bounded_instance() {
+    let directory = std::env::temp_dir().join(format!("aud2-cli-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let instance = directory.join("bounded.json");
+    fs::write(
+        &instance,
+        r#"{"weight_limit": 10, "items": [
+            {"id": 1, "profit": 6, "weight": 2, "max_copies": 3},
+            {"id": 2, "profit": 5, "weight": 3}
+        ]}"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+        .args(["ks-export", "--items-csv"])
+        .arg(&instance)
+        .args(["--model-format", "mps"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let mps = String::from_utf8(output.stdout).unwrap();
+    assert!(mps.contains("'INTORG'"), "{}", mps);
+    assert!(mps.contains(" UP BND x1 3\n"), "{}", mps);
+    assert!(mps.contains(" BV BND x2\n"), "{}", mps);
+
+    // The solvers only support the 0/1 knapsack
+    let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+        .args(["ks-dp", "--items-csv"])
+        .arg(&instance)
+        .output()
+        .unwrap();
+    assert!(output.status.success().not());
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+/// Runs `aud2 --output json subsum-row --sum <sum> --numbers-file <file>` on a file with `content`.
+fn subset_sum_numbers_file(name: &str, content: &str, sum: &str) -> std::process::Output {
+    let directory = std::env::temp_dir().join(format!("aud2-cli-{}-{}", std::process::id(), name));
+    fs::create_dir_all(&directory).unwrap();
+    let numbers_file = directory.join("numbers.csv");
+    fs::write(&numbers_file, content).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+        .args([
+            "--output",
+            "json",
+            "subsum-row",
+            "--sum",
+            sum,
+            "--numbers-file",
+        ])
+        .arg(&numbers_file)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&directory).unwrap();
+    output
+}
+
+#[test]
+fn test_numbers_file() {
+    let output = subset_sum_numbers_file("plain", "5,-3,7\n", "12");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("\"numbers\": [\n    5,\n    -3,\n    7\n  ]"));
+
+    for content in [
+        "5,a\n7,b\n",
+        "number,label\n5,a\n7,b\n",
+        "label,number\na,5\nb,7\n",
+    ] {
+        let output = subset_sum_numbers_file("labeled", content, "12");
+        assert!(output.status.success(), "{:?}", output);
+        assert!(String::from_utf8(output.stdout).unwrap().contains("\"b\""));
+    }
+}
+
+#[test]
+fn test_numbers_file_errors() {
+    for content in [
+        // Mistyped number, which must not be taken as header
+        "1O\n5\n7\n",
+        // Header with unknown columns
+        "value\n5\n7\n",
+        // Labeled lines with a missing label, too many fields or a number as label
+        "5,a\n7\n",
+        "5,a,b\n7,c\n",
+        "5,a\n7,3\n",
+    ] {
+        let output = subset_sum_numbers_file("errors", content, "12");
+        assert!(output.status.success().not(), "{:?}: {:?}", content, output);
+    }
+}
+
+#[test]
+fn test_subset_sum_bitset_witness_opt_in() {
+    let subset_sum_bitset = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+            .args(["--output", "json", "subsum-bitset", "--sum", "14"])
+            .args(args)
+            .args(["3", "5", "9"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let json = subset_sum_bitset(&[]);
+    assert!(json.contains("\"reachable\": true"), "{}", json);
+    assert!(json.contains("\"witness\": null"), "{}", json);
+
+    let json = subset_sum_bitset(&["--witness"]);
+    assert!(
+        json.contains("\"witness\": [\n    1,\n    2\n  ]"),
+        "{}",
+        json
+    );
+}
+
+#[test]
+fn test_solve_output_csv_empty_knapsack() {
+    let directory = std::env::temp_dir().join(format!("aud2-cli-{}-csv", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let instance = directory.join("heavy.json");
+    fs::write(
+        &instance,
+        r#"{"weight_limit": 5, "items": [{"id": 1, "profit": 6, "weight": 7}]}"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+        .args(["--output", "csv", "ks-bb", "--items-csv"])
+        .arg(&instance)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "ids,profit,weight,weight_limit,status,optimal\n,0,0,5,completed,true\n"
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_partition_sum_overflow() {
+    for args in [&["partition-kk"][..], &["partition-kk", "--complete"]] {
+        let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+            .args(args)
+            .args(["18446744073709551615", "1"])
+            .output()
+            .unwrap();
+        assert!(output.status.success().not(), "{:?}", output);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("does not fit into an u64"), "{}", stderr);
+        assert!(stderr.contains("panicked").not(), "{}", stderr);
+    }
+}
+
+#[test]
+fn test_generate_spanner_errors() {
+    for (option, message) in [
+        ("--spanner-items", "at least one spanner item"),
+        (
+            "--spanner-multiplier",
+            "maximum multiplier of a spanner class must be at least 1",
+        ),
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_aud2"))
+            .args(["gen-ks", "--class", "spanner", "-n", "10", option, "0"])
+            .output()
+            .unwrap();
+        assert!(output.status.success().not(), "{:?}", output);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(message), "{}", stderr);
+    }
+}
@@ -0,0 +1,165 @@
+use std::ops::Not;
+
+use aud2::compare::*;
+use aud2::knapsack::Item;
+
+#[test]
+fn test_compare_algorithms() {
+    let items = [
+        Item {
+            id: 0,
+            profit: 14,
+            weight: 11,
+        },
+        Item {
+            id: 1,
+            profit: 6,
+            weight: 5,
+        },
+        Item {
+            id: 2,
+            profit: 13,
+            weight: 13,
+        },
+        Item {
+            id: 3,
+            profit: 16,
+            weight: 18,
+        },
+        Item {
+            id: 4,
+            profit: 9,
+            weight: 7,
+        },
+    ];
+    let report = compare_algorithms(&items, 33, 1, DEFAULT_EXACT_TIME_LIMIT);
+    assert_eq!(report.skipped, []);
+    assert_eq!(report.mismatch, None);
+    let comparisons = report.comparisons;
+    let algorithms: Vec<&str> = comparisons
+        .iter()
+        .map(|comparison| comparison.algorithm.as_str())
+        .collect();
+    assert_eq!(
+        algorithms,
+        [
+            "dynamic_programming",
+            "dp_linear_memory",
+            "branch_and_bound",
+            "meet_in_the_middle",
+            "brute_force",
+            "greedy_k (k=1)",
+            "integer_greedy",
+            "modified_greedy"
+        ]
+    );
+    // The counters are measured, e.g. the dynamic programming computes one row of 34 cells per item
+    let counters: Vec<(&str, u64)> = comparisons
+        .iter()
+        .map(|comparison| (comparison.counter.name, comparison.counter.value))
+        .collect();
+    assert_eq!(counters[0], ("cells", 5 * 34));
+    assert_eq!(counters[4], ("subsets", 32));
+    assert_eq!(counters[7], ("items", 5));
+    assert!(comparisons[0].memory.is_some() && comparisons[3].memory.is_some());
+    for comparison in &comparisons {
+        assert!(comparison.weight <= 33);
+        assert_eq!(comparison.gap, Some(36 - comparison.profit));
+        if comparison.exact {
+            assert_eq!(comparison.profit, 36);
+            assert_eq!(comparison.ratio, Some(1.0));
+        }
+    }
+}
+
+#[test]
+fn test_compare_algorithms_skips_large_instances() {
+    // Too many items for the exponential algorithms and greedy_k, too large weight limit for the dynamic programming
+    let items: Vec<Item> = (0..1000)
+        .map(|id| Item {
+            id,
+            profit: id as u64 % 97 + 1,
+            weight: 1_000_000 + id as u64 % 89,
+        })
+        .collect();
+    let report = compare_algorithms(&items, 500_000_000, 3, DEFAULT_EXACT_TIME_LIMIT);
+    let skipped: Vec<&str> = report
+        .skipped
+        .iter()
+        .map(|skipped| skipped.algorithm.as_str())
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            "dynamic_programming",
+            "dp_linear_memory",
+            "branch_and_bound",
+            "meet_in_the_middle",
+            "brute_force",
+            "greedy_k (k=3)"
+        ]
+    );
+    let algorithms: Vec<&str> = report
+        .comparisons
+        .iter()
+        .map(|comparison| comparison.algorithm.as_str())
+        .collect();
+    assert_eq!(algorithms, ["integer_greedy", "modified_greedy"]);
+    // No exact algorithm was run, so the gap is unknown
+    assert!(report
+        .comparisons
+        .iter()
+        .all(|comparison| comparison.gap.is_none()));
+}
+
+#[test]
+fn test_compare_algorithms_skips_greedy_k_overflow() {
+    // (100 choose 50) does not fit into an u64
+    let items: Vec<Item> = (0..100)
+        .map(|id| Item {
+            id,
+            profit: id as u64 % 7 + 1,
+            weight: id as u64 % 5 + 1,
+        })
+        .collect();
+    let report = compare_algorithms(&items, 50, 50, DEFAULT_EXACT_TIME_LIMIT);
+    let greedy_k = report
+        .skipped
+        .iter()
+        .find(|skipped| skipped.algorithm == "greedy_k (k=50)")
+        .expect("greedy_k is skipped");
+    assert!(greedy_k.reason.starts_with("more than"));
+    assert_eq!(report.mismatch, None);
+}
+
+#[test]
+fn test_compare_algorithms_skips_stopped_exact_algorithms() {
+    let items: Vec<Item> = (0..20)
+        .map(|id| Item {
+            id,
+            profit: id as u64 % 7 + 1,
+            weight: id as u64 % 5 + 1,
+        })
+        .collect();
+    // Every exact algorithm is stopped immediately
+    let report = compare_algorithms(&items, 30, 1, std::time::Duration::ZERO);
+    let skipped: Vec<&str> = report
+        .skipped
+        .iter()
+        .map(|skipped| skipped.algorithm.as_str())
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            "dynamic_programming",
+            "dp_linear_memory",
+            "branch_and_bound",
+            "meet_in_the_middle",
+            "brute_force"
+        ]
+    );
+    assert!(report
+        .comparisons
+        .iter()
+        .all(|comparison| comparison.exact.not() && comparison.gap.is_none()));
+}
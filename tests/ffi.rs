@@ -0,0 +1,66 @@
+//! Compiles the C test harness `tests/ffi/harness.c` against the shared library and runs it. Requires a C compiler,
+//! `cc` or the one in the environment variable `CC`.
+//!
+//! `cargo test` only builds the library as rlib, so the shared library is built by a nested `cargo rustc` in a separate
+//! target directory.
+#![cfg(all(feature = "ffi", unix))]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn test_committed_header_is_up_to_date() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let generated = fs::read_to_string(PathBuf::from(env!("OUT_DIR")).join("aud2.h")).unwrap();
+    let committed = fs::read_to_string(manifest_dir.join("include/aud2.h")).unwrap();
+    assert!(
+        generated == committed,
+        "include/aud2.h is outdated, update it with AUD2_UPDATE_HEADER=1 cargo build --features ffi"
+    );
+}
+
+#[test]
+fn test_c_harness() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    let status = Command::new(env!("CARGO"))
+        .args(["rustc", "--lib", "--features", "ffi"])
+        .args(["--crate-type", "cdylib", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(&manifest_dir)
+        .status()
+        .expect("Run cargo");
+    assert!(status.success(), "Building the shared library failed");
+    let library_dir = target_dir.join("debug");
+    let harness = target_dir.join("harness");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(compiler)
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(manifest_dir.join("tests/ffi/harness.c"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-laud2")
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-o")
+        .arg(&harness)
+        .status()
+        .expect("Run C compiler");
+    assert!(status.success(), "Compiling the C harness failed");
+
+    // cargo test sets LD_LIBRARY_PATH to target/<profile>, which may contain a library built without the ffi feature
+    let output = Command::new(&harness)
+        .env_remove("LD_LIBRARY_PATH")
+        .output()
+        .expect("Run C harness");
+    assert!(
+        output.status.success(),
+        "C harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
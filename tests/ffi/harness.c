@@ -0,0 +1,96 @@
+/* Test harness for the C interface of aud2, compiled and run by tests/ffi.rs. */
+
+#include <stdio.h>
+
+#include "aud2.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                       \
+  do {                                                                         \
+    if (!(condition)) {                                                        \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,        \
+              #condition);                                                     \
+      failures++;                                                              \
+    }                                                                          \
+  } while (0)
+
+static const size_t IDS[] = {1, 2, 3, 4};
+static const uint64_t PROFITS[] = {3, 3, 10, 5};
+static const uint64_t WEIGHTS[] = {20, 32, 40, 8};
+static const size_t LEN = 4;
+
+/* The optimal knapsack for a weight limit of 60 consists of the items 3 and 4. */
+static void check_optimal(struct Aud2KnapsackResult result) {
+  CHECK(result.status == AUD2_STATUS_OK);
+  CHECK(result.len == 2);
+  CHECK(result.profit == 15);
+  CHECK(result.weight == 48);
+  size_t id_sum = 0;
+  for (size_t i = 0; i < result.len; i++) {
+    id_sum += result.ids[i];
+  }
+  CHECK(id_sum == 7);
+  aud2_knapsack_result_free(&result);
+  CHECK(result.ids == NULL);
+  CHECK(result.len == 0);
+}
+
+int main(void) {
+  check_optimal(aud2_knapsack_dynamic_programming(IDS, PROFITS, WEIGHTS, LEN, 60));
+  check_optimal(aud2_knapsack_branch_and_bound(IDS, PROFITS, WEIGHTS, LEN, 60));
+  check_optimal(aud2_knapsack_meet_in_the_middle(IDS, PROFITS, WEIGHTS, LEN, 60));
+  check_optimal(aud2_knapsack_greedy_k(IDS, PROFITS, WEIGHTS, LEN, 60, 2));
+
+  struct Aud2KnapsackResult greedy = aud2_knapsack_integer_greedy(IDS, PROFITS, WEIGHTS, LEN, 60);
+  CHECK(greedy.status == AUD2_STATUS_OK);
+  CHECK(greedy.weight <= 60);
+  aud2_knapsack_result_free(&greedy);
+
+  struct Aud2KnapsackResult modified = aud2_knapsack_modified_greedy(IDS, PROFITS, WEIGHTS, LEN, 60);
+  CHECK(modified.status == AUD2_STATUS_OK);
+  CHECK(modified.profit * 2 >= 15);
+  aud2_knapsack_result_free(&modified);
+
+  /* Items 4 and 3 fit completely, then 12 of the remaining weight go to item 1 */
+  struct Aud2FractionalResult fractional =
+      aud2_knapsack_fractional_greedy(IDS, PROFITS, WEIGHTS, LEN, 60);
+  CHECK(fractional.status == AUD2_STATUS_OK);
+  CHECK(fractional.len == 3);
+  CHECK(fractional.ids[2] == 1);
+  CHECK(fractional.numerators[2] == 3 && fractional.denominators[2] == 5);
+  aud2_fractional_result_free(&fractional);
+  CHECK(fractional.ids == NULL && fractional.numerators == NULL && fractional.denominators == NULL);
+
+  /* Empty and invalid inputs */
+  struct Aud2KnapsackResult empty = aud2_knapsack_dynamic_programming(NULL, NULL, NULL, 0, 60);
+  CHECK(empty.status == AUD2_STATUS_OK && empty.len == 0 && empty.ids == NULL);
+  aud2_knapsack_result_free(&empty);
+  struct Aud2KnapsackResult null = aud2_knapsack_dynamic_programming(IDS, NULL, WEIGHTS, LEN, 60);
+  CHECK(null.status == AUD2_STATUS_NULL_POINTER);
+  aud2_knapsack_result_free(&null);
+  aud2_knapsack_result_free(NULL);
+
+  /* The total profit overflows, which panics in the debug build instead of aborting the harness */
+  const size_t overflow_ids[] = {1, 2};
+  const uint64_t overflow_profits[] = {UINT64_MAX, 1};
+  const uint64_t overflow_weights[] = {1, 1};
+  struct Aud2KnapsackResult overflow =
+      aud2_knapsack_integer_greedy(overflow_ids, overflow_profits, overflow_weights, 2, 10);
+  CHECK(overflow.status == AUD2_STATUS_PANIC && overflow.ids == NULL);
+  aud2_knapsack_result_free(&overflow);
+
+  const uint64_t numbers[] = {3, 5, 9};
+  struct Aud2SubsetSumResult reachable = aud2_subset_sum_set(numbers, 3, 14);
+  CHECK(reachable.status == AUD2_STATUS_OK && reachable.reachable);
+  struct Aud2SubsetSumResult unreachable = aud2_subset_sum_vec(numbers, 3, 13);
+  CHECK(unreachable.status == AUD2_STATUS_OK && !unreachable.reachable);
+  CHECK(aud2_subset_sum_vec(NULL, 3, 13).status == AUD2_STATUS_NULL_POINTER);
+
+  if (failures > 0) {
+    fprintf(stderr, "%d checks failed\n", failures);
+    return 1;
+  }
+  printf("all checks passed\n");
+  return 0;
+}
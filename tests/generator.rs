@@ -0,0 +1,109 @@
+use aud2::generator::*;
+
+/// All instance classes, including a spanner class.
+fn instance_classes() -> Vec<InstanceClass> {
+    vec![
+        InstanceClass::Uncorrelated,
+        InstanceClass::WeaklyCorrelated,
+        InstanceClass::StronglyCorrelated,
+        InstanceClass::InverseStronglyCorrelated,
+        InstanceClass::AlmostStronglyCorrelated,
+        InstanceClass::SubsetSum,
+        InstanceClass::Spanner {
+            base: Box::new(InstanceClass::Uncorrelated),
+            spanner_items: 3,
+            max_multiplier: 10,
+        },
+    ]
+}
+
+#[test]
+fn test_generate_reproducible() {
+    for class in instance_classes() {
+        let items = generate(&class, 50, 1000, 7).unwrap();
+        assert_eq!(items.len(), 50);
+        assert_eq!(
+            items,
+            generate(&class, 50, 1000, 7).unwrap(),
+            "class={}",
+            class
+        );
+        assert_ne!(
+            items,
+            generate(&class, 50, 1000, 8).unwrap(),
+            "class={}",
+            class
+        );
+        assert!(items
+            .iter()
+            .all(|item| item.profit >= 1 && item.weight >= 1));
+    }
+}
+
+#[test]
+fn test_generate_correlation() {
+    let range = 1000;
+    for item in generate(&InstanceClass::StronglyCorrelated, 100, range, 1).unwrap() {
+        assert!((1..=range).contains(&item.weight));
+        assert_eq!(item.profit, item.weight + range / 10);
+    }
+    for item in generate(&InstanceClass::InverseStronglyCorrelated, 100, range, 1).unwrap() {
+        assert!((1..=range).contains(&item.profit));
+        assert_eq!(item.weight, item.profit + range / 10);
+    }
+    for item in generate(&InstanceClass::WeaklyCorrelated, 100, range, 1).unwrap() {
+        assert!(item.profit + range / 10 >= item.weight && item.profit <= item.weight + range / 10);
+    }
+}
+
+#[test]
+fn test_parse_instance_class() {
+    for class in instance_classes() {
+        let parsed: InstanceClass = class.to_string().parse().unwrap();
+        assert_eq!(parsed.to_string(), class.to_string());
+    }
+    assert!("correlated".parse::<InstanceClass>().is_err());
+}
+
+#[test]
+fn test_generate_nested_spanner() {
+    let class = InstanceClass::Spanner {
+        base: Box::new(InstanceClass::Spanner {
+            base: Box::new(InstanceClass::Uncorrelated),
+            spanner_items: 2,
+            max_multiplier: 10,
+        }),
+        spanner_items: 2,
+        max_multiplier: 10,
+    };
+    assert_eq!(
+        generate(&class, 10, 1000, 1),
+        Err(SpannerError::NestedSpanner)
+    );
+}
+
+#[test]
+fn test_generate_no_spanner_items() {
+    let class = InstanceClass::Spanner {
+        base: Box::new(InstanceClass::Uncorrelated),
+        spanner_items: 0,
+        max_multiplier: 10,
+    };
+    assert_eq!(
+        generate(&class, 10, 1000, 1),
+        Err(SpannerError::NoSpannerItems)
+    );
+}
+
+#[test]
+fn test_generate_no_multipliers() {
+    let class = InstanceClass::Spanner {
+        base: Box::new(InstanceClass::Uncorrelated),
+        spanner_items: 2,
+        max_multiplier: 0,
+    };
+    assert_eq!(
+        generate(&class, 10, 1000, 1),
+        Err(SpannerError::NoMultipliers)
+    );
+}
@@ -173,6 +173,30 @@ fn test_dynamic_programming() {
     );
 }
 
+#[test]
+fn test_dynamic_programming_linear_memory() {
+    let weight_limit = 120;
+    let actual_knapsack = dynamic_programming_linear_memory(&ITEMS, weight_limit);
+    assert!(
+        actual_knapsack.iter().map(|item| item.weight).sum::<u64>() <= weight_limit,
+        "Knapsack solution too heavy"
+    );
+    assert_eq!(
+        knapsack_profit(&actual_knapsack),
+        knapsack_profit(&dynamic_programming(&ITEMS, weight_limit))
+    );
+
+    // A budget of 0 bytes forces the linear memory variant
+    assert_eq!(
+        dynamic_programming_with_memory_budget(&ITEMS, weight_limit, 0),
+        actual_knapsack
+    );
+    assert_eq!(
+        dynamic_programming_with_memory_budget(&ITEMS, weight_limit, u64::MAX),
+        dynamic_programming(&ITEMS, weight_limit)
+    );
+}
+
 #[test]
 fn test_greedy_k() {
     let items = [
@@ -299,3 +323,407 @@ fn test_branch_and_bound_2() {
     let expected_knapsack = [&items[2], &items[4], &items[0]];
     assert_eq!(actual_knapsack, expected_knapsack);
 }
+
+#[test]
+fn test_meet_in_the_middle() {
+    let items = [
+        Item {
+            id: 0,
+            profit: 6,
+            weight: 2_000_000_000_000,
+        },
+        Item {
+            id: 1,
+            profit: 5,
+            weight: 3_000_000_000_000,
+        },
+        Item {
+            id: 2,
+            profit: 8,
+            weight: 6_000_000_000_000,
+        },
+        Item {
+            id: 3,
+            profit: 9,
+            weight: 7_000_000_000_000,
+        },
+        Item {
+            id: 4,
+            profit: 6,
+            weight: 5_000_000_000_000,
+        },
+        Item {
+            id: 5,
+            profit: 7,
+            weight: 9_000_000_000_000,
+        },
+        Item {
+            id: 6,
+            profit: 3,
+            weight: 4_000_000_000_000,
+        },
+    ];
+    let actual_knapsack = meet_in_the_middle(&items, 9_000_000_000_000).unwrap();
+    let expected_knapsack = [&items[0], &items[3]];
+    assert_eq!(actual_knapsack, expected_knapsack);
+}
+
+#[test]
+fn test_meet_in_the_middle_too_many_items() {
+    let items: Vec<Item> = (0..=MEET_IN_THE_MIDDLE_MAX_ITEMS)
+        .map(|id| Item {
+            id,
+            profit: 1,
+            weight: 1,
+        })
+        .collect();
+    assert_eq!(
+        meet_in_the_middle(&items, 10),
+        Err(TooManyItemsError {
+            item_count: MEET_IN_THE_MIDDLE_MAX_ITEMS + 1,
+            max_item_count: MEET_IN_THE_MIDDLE_MAX_ITEMS,
+        })
+    );
+}
+
+#[test]
+fn test_brute_force() {
+    let items = [
+        Item {
+            id: 0,
+            profit: 14,
+            weight: 11,
+        },
+        Item {
+            id: 1,
+            profit: 6,
+            weight: 5,
+        },
+        Item {
+            id: 2,
+            profit: 13,
+            weight: 13,
+        },
+        Item {
+            id: 3,
+            profit: 16,
+            weight: 18,
+        },
+        Item {
+            id: 4,
+            profit: 9,
+            weight: 7,
+        },
+    ];
+    let actual_knapsack = brute_force(&items, 33).unwrap();
+    let expected_knapsack = [&items[0], &items[2], &items[4]];
+    assert_eq!(actual_knapsack, expected_knapsack);
+}
+
+#[test]
+fn test_branch_and_bound_with_statistics() {
+    let actual_knapsack = branch_and_bound(&ITEMS, 120);
+    let (knapsack, statistics) = branch_and_bound_with_statistics(&ITEMS, 120);
+    assert_eq!(knapsack, actual_knapsack);
+    assert!(statistics.explored_nodes > 0);
+    assert!(statistics.pruned_nodes <= statistics.explored_nodes);
+}
+
+#[test]
+fn test_integer_greedy_with_strategy() {
+    let weight_capacity = 120;
+    let actual_knapsack =
+        integer_greedy_with_strategy(&ITEMS, weight_capacity, GreedyStrategy::Profit);
+    let expected_ids = [3, 13, 15, 12, 6];
+    assert_eq!(
+        actual_knapsack
+            .iter()
+            .map(|item| item.id)
+            .collect::<Vec<_>>(),
+        expected_ids
+    );
+
+    let actual_knapsack =
+        integer_greedy_with_strategy(&ITEMS, weight_capacity, GreedyStrategy::Weight);
+    let expected_ids = [6, 4, 9, 5, 13, 1, 12, 14];
+    assert_eq!(
+        actual_knapsack
+            .iter()
+            .map(|item| item.id)
+            .collect::<Vec<_>>(),
+        expected_ids
+    );
+}
+
+#[test]
+fn test_modified_greedy() {
+    let items = [
+        Item {
+            id: 0,
+            profit: 2,
+            weight: 1,
+        },
+        Item {
+            id: 1,
+            profit: 10,
+            weight: 10,
+        },
+    ];
+    assert_eq!(integer_greedy(&items, 10), [&items[0]]);
+    assert_eq!(modified_greedy(&items, 10), [&items[1]]);
+    assert_eq!(modified_greedy(&ITEMS, 120), integer_greedy(&ITEMS, 120));
+}
+
+#[test]
+fn test_verify() {
+    let weight_limit = 120;
+    // Optimal solution, proven by dynamic programming
+    let verification = verify(&ITEMS, weight_limit, &[4, 6, 9, 12, 13, 15, 16], true);
+    assert!(verification.is_feasible());
+    assert_eq!(verification.profit, 44);
+    assert_eq!(verification.weight, 120);
+    assert_eq!(verification.optimality, Optimality::Optimal);
+
+    // Feasible, but not optimal
+    let verification = verify(&ITEMS, weight_limit, &[6, 4, 13], true);
+    assert!(verification.is_feasible());
+    assert_eq!(
+        verification.optimality,
+        Optimality::NotOptimal { optimal_profit: 44 }
+    );
+    assert_eq!(
+        verification.upper_bound_gap(),
+        verification.upper_bound - 19
+    );
+    let verification = verify(&ITEMS, weight_limit, &[6, 4, 13], false);
+    assert_eq!(verification.optimality, Optimality::Unknown);
+
+    // Infeasible
+    let verification = verify(&ITEMS, 30, &[6, 4, 4, 4, 99, 3], true);
+    assert_eq!(
+        verification.infeasibilities,
+        [
+            Infeasibility::DuplicateId(4),
+            Infeasibility::UnknownId(99),
+            Infeasibility::OverCapacity {
+                weight: 52,
+                weight_limit: 30
+            }
+        ]
+    );
+    assert_eq!(verification.optimality, Optimality::Unknown);
+}
+
+#[test]
+fn test_incremental_knapsack() {
+    let weight_limit = 120;
+    let mut knapsack = IncrementalKnapsack::new(weight_limit);
+    for (i, item) in ITEMS.iter().enumerate() {
+        knapsack.add(item.clone());
+        let expected_profit = knapsack_profit(&dynamic_programming(&ITEMS[..=i], weight_limit));
+        assert_eq!(knapsack.optimum(), expected_profit);
+        let selection = knapsack.selection();
+        assert_eq!(knapsack_profit(&selection), expected_profit);
+        assert!(selection.iter().map(|item| item.weight).sum::<u64>() <= weight_limit);
+    }
+    assert_eq!(knapsack.len(), ITEMS.len());
+
+    // Undo the last item
+    assert_eq!(knapsack.undo(), Some(ITEMS[15].clone()));
+    assert_eq!(
+        knapsack.optimum(),
+        knapsack_profit(&dynamic_programming(&ITEMS[..15], weight_limit))
+    );
+
+    // Remove an item in the middle
+    assert_eq!(knapsack.remove(13), Some(ITEMS[12].clone()));
+    assert_eq!(knapsack.remove(13), None);
+    let remaining_items: Vec<&Item> = knapsack.items().collect();
+    assert_eq!(remaining_items.len(), 14);
+    assert_eq!(
+        knapsack.optimum(),
+        knapsack_profit(&dynamic_programming(remaining_items, weight_limit))
+    );
+}
+
+#[test]
+fn test_solve_options_cancel() {
+    let weight_limit = 120;
+    let cancellation_token = CancellationToken::new();
+    cancellation_token.cancel();
+    let options = SolveOptions {
+        cancellation_token: Some(cancellation_token),
+        ..SolveOptions::default()
+    };
+    let result = dynamic_programming_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    assert!(!result.optimal);
+    assert!(result.knapsack.is_empty());
+    let (result, statistics) = branch_and_bound_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    assert_eq!(statistics.explored_nodes, 0);
+    let result = greedy_k_with_options(&ITEMS, weight_limit, 2, &options);
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    let result = brute_force_with_options(&ITEMS, weight_limit, &options).unwrap();
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    let (result, statistics) =
+        dynamic_programming_linear_memory_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    assert!(result.knapsack.is_empty());
+    assert_eq!(statistics.computed_cells, 0);
+    for memory_budget in [0, u64::MAX] {
+        let result = dynamic_programming_with_memory_budget_with_options(
+            &ITEMS,
+            weight_limit,
+            memory_budget,
+            &options,
+        );
+        assert_eq!(result.status, SolveStatus::Cancelled);
+        assert!(result.knapsack.is_empty());
+    }
+    let (result, statistics) =
+        meet_in_the_middle_with_options(&ITEMS, weight_limit, &options).unwrap();
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    assert!(result.knapsack.is_empty());
+    // Only the empty subset of the first half is known
+    assert_eq!(statistics.enumerated_subsets, 1);
+    let result = integer_greedy_with_options(
+        &ITEMS,
+        weight_limit,
+        GreedyStrategy::WeightProfitRatio,
+        &options,
+    );
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    assert!(result.knapsack.is_empty());
+    let result = modified_greedy_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::Cancelled);
+
+    // Cancel after some rows. The result is the optimum for the rows calculated so far.
+    let cancellation_token = CancellationToken::new();
+    let options = SolveOptions {
+        cancellation_token: Some(cancellation_token.clone()),
+        progress: Some(Box::new(|progress| {
+            if progress == (Progress::Rows { done: 4, total: 16 }) {
+                cancellation_token.cancel();
+            }
+        })),
+        ..SolveOptions::default()
+    };
+    let result = dynamic_programming_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::Cancelled);
+    assert!(!result.optimal);
+    assert_eq!(
+        result.knapsack,
+        dynamic_programming(&ITEMS[..4], weight_limit)
+    );
+}
+
+#[test]
+fn test_solve_options_deadline_and_progress() {
+    let weight_limit = 120;
+    let options = SolveOptions {
+        deadline: Some(std::time::Instant::now()),
+        ..SolveOptions::default()
+    };
+    let (result, _) = branch_and_bound_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::DeadlineExceeded);
+    assert!(!result.optimal);
+
+    let last_progress = std::cell::Cell::new(None);
+    let options = SolveOptions {
+        progress: Some(Box::new(|progress: Progress| {
+            last_progress.set(progress.percent())
+        })),
+        ..SolveOptions::default()
+    };
+    let result = greedy_k_with_options(&ITEMS, weight_limit, 1, &options);
+    assert_eq!(result.status, SolveStatus::Completed);
+    assert!(!result.optimal, "greedy_k is not an exact solver");
+    assert_eq!(last_progress.get(), Some(100.0));
+    let result = dynamic_programming_with_options(&ITEMS, weight_limit, &options);
+    assert!(result.optimal);
+    assert_eq!(last_progress.get(), Some(100.0));
+    let (result, statistics) =
+        dynamic_programming_linear_memory_with_options(&ITEMS, weight_limit, &options);
+    assert!(result.optimal);
+    // The top level alone calculates a row for each item
+    assert!(statistics.computed_cells >= ITEMS.len() as u64 * (weight_limit + 1));
+    assert_eq!(statistics.peak_row_cells, 2 * (weight_limit + 1));
+    assert_eq!(
+        knapsack_profit(&result.knapsack),
+        knapsack_profit(&dynamic_programming(&ITEMS, weight_limit))
+    );
+    assert_eq!(last_progress.get(), Some(100.0));
+    let (result, statistics) =
+        meet_in_the_middle_with_options(&ITEMS, weight_limit, &options).unwrap();
+    assert!(result.optimal);
+    assert!(statistics.remaining_subsets <= statistics.enumerated_subsets);
+    assert!(statistics.enumerated_subsets <= 2 * (1 << (ITEMS.len() / 2)));
+    assert_eq!(last_progress.get(), Some(100.0));
+    let result = modified_greedy_with_options(&ITEMS, weight_limit, &options);
+    assert_eq!(result.status, SolveStatus::Completed);
+    assert!(!result.optimal, "modified_greedy is not an exact solver");
+    assert_eq!(result.knapsack, modified_greedy(&ITEMS, weight_limit));
+    assert_eq!(last_progress.get(), Some(100.0));
+}
+
+#[test]
+fn test_branch_and_bound_with_incumbent() {
+    let weight_limit = 120;
+    let options = SolveOptions::default();
+    let (cold_result, cold_statistics) =
+        branch_and_bound_with_options(&ITEMS, weight_limit, &options);
+    let incumbent_ids: Vec<usize> = greedy_k(&ITEMS, weight_limit, 1)
+        .iter()
+        .map(|item| item.id)
+        .collect();
+    let (warm_result, warm_statistics) =
+        branch_and_bound_with_incumbent(&ITEMS, weight_limit, &incumbent_ids, &options).unwrap();
+    assert!(warm_result.optimal);
+    assert_eq!(
+        knapsack_profit(&warm_result.knapsack),
+        knapsack_profit(&cold_result.knapsack)
+    );
+    assert!(warm_statistics.explored_nodes <= cold_statistics.explored_nodes);
+
+    // Infeasible incumbent
+    assert_eq!(
+        branch_and_bound_with_incumbent(&ITEMS, 10, &[3, 42], &options),
+        Err(InfeasibleIncumbentError(vec![
+            Infeasibility::UnknownId(42),
+            Infeasibility::OverCapacity {
+                weight: 40,
+                weight_limit: 10
+            }
+        ]))
+    );
+}
+
+#[test]
+fn test_serialize() {
+    let solution = Solution::new(dynamic_programming(&ITEMS[..3], 60), 60);
+    assert_eq!(
+        serde_json::to_value(&solution).unwrap(),
+        serde_json::json!({
+            "items": [
+                {"id": 1, "profit": 3, "weight": 20},
+                {"id": 3, "profit": 10, "weight": 40},
+            ],
+            "profit": 13,
+            "weight": 60,
+            "weight_limit": 60,
+        })
+    );
+
+    let partial_packed_item = PartialPackedItem {
+        item: &ITEMS[0],
+        take_ratio: Fraction::new(3u64, 4u64),
+    };
+    assert_eq!(
+        serde_json::to_value(&partial_packed_item).unwrap(),
+        serde_json::json!({
+            "item": {"id": 1, "profit": 3, "weight": 20},
+            "take_ratio": "3/4",
+        })
+    );
+}
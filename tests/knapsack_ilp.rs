@@ -0,0 +1,114 @@
+use aud2::knapsack::ilp::*;
+use aud2::knapsack::Item;
+
+#[test]
+fn test_write_lp() {
+    let items = [
+        Item {
+            id: 0,
+            profit: 6,
+            weight: 2,
+        },
+        Item {
+            id: 1,
+            profit: 5,
+            weight: 3,
+        },
+    ];
+    let mut lp = Vec::new();
+    IlpModel::from_items(&items, 4).write_lp(&mut lp).unwrap();
+    let expected_lp = "\\ Knapsack instance exported by aud2
+Maximize
+ obj: 6 x0 + 5 x1
+Subject To
+ c0: 2 x0 + 3 x1
+   <= 4
+Binary
+ x0
+ x1
+End
+";
+    assert_eq!(String::from_utf8(lp).unwrap(), expected_lp);
+}
+
+#[test]
+fn test_write_mps_bounded_multi_dimensional() {
+    let model = IlpModel {
+        items: vec![
+            IlpItem {
+                id: 1,
+                profit: 3,
+                weights: vec![2, 5],
+                max_copies: 1,
+            },
+            IlpItem {
+                id: 2,
+                profit: 4,
+                weights: vec![3, 1],
+                max_copies: 3,
+            },
+        ],
+        weight_limits: vec![10, 7],
+    };
+    let mut mps = Vec::new();
+    model.write_mps(&mut mps).unwrap();
+    let expected_mps = "NAME knapsack
+OBJSENSE
+    MAX
+ROWS
+ N obj
+ L c0
+ L c1
+COLUMNS
+    MARKER                 'MARKER'                 'INTORG'
+ x1 obj 3 c0 2
+ x1 c1 5
+ x2 obj 4 c0 3
+ x2 c1 1
+    MARKER                 'MARKER'                 'INTEND'
+RHS
+ RHS c0 10
+ RHS c1 7
+BOUNDS
+ BV BND x1
+ UP BND x2 3
+ENDATA
+";
+    assert_eq!(String::from_utf8(mps).unwrap(), expected_mps);
+
+    let mut lp = Vec::new();
+    model.write_lp(&mut lp).unwrap();
+    let lp = String::from_utf8(lp).unwrap();
+    assert!(lp.contains("Bounds\n 0 <= x2 <= 3\nGeneral\n x2\nBinary\n x1\n"));
+
+    // Every item needs a weight per weight limit
+    let invalid_model = IlpModel {
+        weight_limits: vec![10],
+        ..model
+    };
+    assert!(invalid_model.write_lp(Vec::new()).is_err());
+}
+
+#[test]
+fn test_read_solution() {
+    let glpk_solution = "Problem:
+Rows:       1
+Columns:    3 (3 integer, 3 binary)
+Status:     INTEGER OPTIMAL
+Objective:  obj = 15 (MAXimum)
+
+   No.   Row name        Activity     Lower bound   Upper bound
+------ ------------    ------------- ------------- -------------
+     1 c0                          9                           9
+
+   No. Column name       Activity     Lower bound   Upper bound
+------ ------------    ------------- ------------- -------------
+     1 x0           *              1             0             1
+     2 x1           *              0             0             1
+     3 x3           *              1             0             1
+";
+    assert_eq!(read_solution(glpk_solution), [0, 3]);
+
+    let bounded_solution = "x4 2\nx7 0\nx9 1.0000001\n";
+    assert_eq!(read_solution(bounded_solution), [4, 4, 9]);
+}
@@ -0,0 +1,181 @@
+use std::ops::Not;
+
+use aud2::knapsack::ilp::{IlpItem, IlpModel};
+use aud2::knapsack::instance::*;
+use aud2::knapsack::Item;
+
+fn item(id: usize, profit: u64, weight: u64) -> Item {
+    Item { id, profit, weight }
+}
+
+#[test]
+fn test_parse_pisinger() {
+    let text = "knapPI_1_3_1000_1
+n 3
+c 10
+z 11
+time 0.00
+1,5,4,1
+2,6,6,1
+3,3,5,0
+-----
+
+knapPI_1_3_1000_2
+n 2
+c 7
+z 9
+time 0.00
+1,9,7,1
+2,1,1,0
+-----
+
+";
+    assert!(is_pisinger(text));
+    assert!(is_or_library(text).not());
+    let instances = parse_pisinger(text).unwrap();
+    assert_eq!(
+        instances,
+        vec![
+            Instance {
+                weight_limit: 10,
+                items: vec![item(1, 5, 4), item(2, 6, 6), item(3, 3, 5)],
+            },
+            Instance {
+                weight_limit: 7,
+                items: vec![item(1, 9, 7), item(2, 1, 1)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_pisinger_errors() {
+    let wrong_count = "knapPI\nn 3\nc 10\n1,5,4,0\n-----\n";
+    assert_eq!(
+        parse_pisinger(wrong_count).unwrap_err().to_string(),
+        "line 1: expected 3 items, but got 1"
+    );
+    let invalid_item = "knapPI\nn 1\nc 10\n1,x,4,0\n";
+    assert_eq!(parse_pisinger(invalid_item).unwrap_err().line, Some(4));
+    assert!(parse_pisinger("").is_err());
+}
+
+#[test]
+fn test_parse_or_library() {
+    let text = " 2
+ 3 2 0
+ 10 20 30
+ 1 2 3
+ 3 2 1
+ 4 5
+ 1 1 7.000
+ 4
+ 2
+ 3
+";
+    assert!(is_or_library(text));
+    assert!(is_pisinger(text).not());
+    let models = parse_or_library(text).unwrap();
+    assert_eq!(
+        models[0],
+        IlpModel {
+            items: vec![
+                IlpItem {
+                    id: 1,
+                    profit: 10,
+                    weights: vec![1, 3],
+                    max_copies: 1,
+                },
+                IlpItem {
+                    id: 2,
+                    profit: 20,
+                    weights: vec![2, 2],
+                    max_copies: 1,
+                },
+                IlpItem {
+                    id: 3,
+                    profit: 30,
+                    weights: vec![3, 1],
+                    max_copies: 1,
+                },
+            ],
+            weight_limits: vec![4, 5],
+        }
+    );
+    assert_eq!(models[0].to_instance(), None);
+    assert_eq!(
+        models[1].to_instance(),
+        Some(Instance {
+            weight_limit: 3,
+            items: vec![item(1, 4, 2)],
+        })
+    );
+
+    let truncated = "1\n3 1 0\n10 20";
+    assert_eq!(
+        parse_or_library(truncated).unwrap_err().to_string(),
+        "line 3: unexpected end of file, expected a profit"
+    );
+}
+
+#[test]
+fn test_parse_json_and_toml() {
+    let expected = Instance {
+        weight_limit: 5,
+        items: vec![item(1, 6, 2), item(2, 5, 3)],
+    };
+    let json = r#"{"weight_limit": 5, "items": [
+        {"id": 1, "profit": 6, "weight": 2},
+        {"id": 2, "profit": 5, "weight": 3}
+    ]}"#;
+    assert_eq!(parse_json(json).unwrap(), expected);
+    let toml = "weight_limit = 5
+
+[[items]]
+id = 1
+profit = 6
+weight = 2
+
+[[items]]
+id = 2
+profit = 5
+weight = 3
+";
+    assert_eq!(parse_toml(toml).unwrap(), expected);
+
+    let missing_limit = r#"{"items": []}"#;
+    assert!(parse_json(missing_limit).is_err());
+    assert!(parse_toml("items = []").is_err());
+}
+
+#[test]
+fn test_parse_bounded_json_and_toml() {
+    let json = r#"{"weight_limit": 5, "items": [
+        {"id": 1, "profit": 6, "weight": 2, "max_copies": 3},
+        {"id": 2, "profit": 5, "weight": 3}
+    ]}"#;
+    let model = parse_json_model(json).unwrap();
+    assert_eq!(
+        model
+            .items
+            .iter()
+            .map(|item| item.max_copies)
+            .collect::<Vec<_>>(),
+        [3, 1]
+    );
+    assert_eq!(model.to_instance(), None);
+    assert!(parse_json(json).is_err());
+
+    let toml = "weight_limit = 5
+
+[[items]]
+id = 1
+profit = 6
+weight = 2
+max_copies = 3
+";
+    let model = parse_toml_model(toml).unwrap();
+    assert_eq!(model.items[0].max_copies, 3);
+    assert_eq!(model.weight_limits, [5]);
+    assert!(parse_toml(toml).is_err());
+}
@@ -0,0 +1,107 @@
+//! Property-based tests that check the knapsack algorithms against the [brute_force] reference solver on random
+//! instances.
+
+use aud2::knapsack::*;
+use fraction::Fraction;
+use proptest::prelude::*;
+
+/// Generates small random knapsack instances, i.e. a list of items and a weight limit.
+fn instance() -> impl Strategy<Value = (Vec<Item>, u64)> {
+    let items = prop::collection::vec((1..=50u64, 1..=50u64), 0..=12).prop_map(|profits_weights| {
+        profits_weights
+            .into_iter()
+            .enumerate()
+            .map(|(id, (profit, weight))| Item { id, profit, weight })
+            .collect::<Vec<_>>()
+    });
+    (items, 0..=200u64)
+}
+
+/// Calculates the total weight of all items.
+fn knapsack_weight(knapsack: &[&Item]) -> u64 {
+    knapsack.iter().map(|item| item.weight).sum()
+}
+
+proptest! {
+    #[test]
+    fn dynamic_programming_is_optimal((items, weight_limit) in instance()) {
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let knapsack = dynamic_programming(&items, weight_limit);
+        prop_assert_eq!(knapsack_profit(&knapsack), knapsack_profit(&optimum));
+    }
+
+    #[test]
+    fn dynamic_programming_linear_memory_is_optimal((items, weight_limit) in instance()) {
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let knapsack = dynamic_programming_linear_memory(&items, weight_limit);
+        prop_assert_eq!(knapsack_profit(&knapsack), knapsack_profit(&optimum));
+    }
+
+    #[test]
+    fn branch_and_bound_is_optimal((items, weight_limit) in instance()) {
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let knapsack = branch_and_bound(&items, weight_limit);
+        prop_assert_eq!(knapsack_profit(&knapsack), knapsack_profit(&optimum));
+    }
+
+    #[test]
+    fn meet_in_the_middle_is_optimal((items, weight_limit) in instance()) {
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let knapsack = meet_in_the_middle(&items, weight_limit).unwrap();
+        prop_assert_eq!(knapsack_profit(&knapsack), knapsack_profit(&optimum));
+    }
+
+    #[test]
+    fn greedy_k_within_approximation_bound((items, weight_limit) in instance(), k in 1..=3usize) {
+        // greedy_k guarantees at least k/(k+1) of the optimal profit
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let knapsack = greedy_k(&items, weight_limit, k);
+        prop_assert!(
+            (k as u64 + 1) * knapsack_profit(&knapsack) >= k as u64 * knapsack_profit(&optimum),
+            "greedy_k profit={} optimum={} k={}",
+            knapsack_profit(&knapsack),
+            knapsack_profit(&optimum),
+            k
+        );
+    }
+
+    #[test]
+    fn modified_greedy_within_approximation_bound((items, weight_limit) in instance()) {
+        // modified_greedy guarantees at least half of the optimal profit
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let knapsack = modified_greedy(&items, weight_limit);
+        prop_assert!(2 * knapsack_profit(&knapsack) >= knapsack_profit(&optimum));
+    }
+
+    #[test]
+    fn fractional_greedy_is_upper_bound((items, weight_limit) in instance()) {
+        let optimum = brute_force(&items, weight_limit).unwrap();
+        let fractional_profit: Fraction = fractional_greedy(&items, weight_limit)
+            .iter()
+            .map(PartialPackedItem::effective_profit)
+            .sum();
+        prop_assert!(fractional_profit >= Fraction::from(knapsack_profit(&optimum)));
+    }
+
+    #[test]
+    fn solutions_respect_capacity((items, weight_limit) in instance(), k in 0..=3usize) {
+        prop_assert!(knapsack_weight(&brute_force(&items, weight_limit).unwrap()) <= weight_limit);
+        prop_assert!(knapsack_weight(&dynamic_programming(&items, weight_limit)) <= weight_limit);
+        prop_assert!(knapsack_weight(&dynamic_programming_linear_memory(&items, weight_limit)) <= weight_limit);
+        prop_assert!(knapsack_weight(&branch_and_bound(&items, weight_limit)) <= weight_limit);
+        prop_assert!(knapsack_weight(&meet_in_the_middle(&items, weight_limit).unwrap()) <= weight_limit);
+        prop_assert!(knapsack_weight(&integer_greedy(&items, weight_limit)) <= weight_limit);
+        prop_assert!(knapsack_weight(&greedy_k(&items, weight_limit, k)) <= weight_limit);
+        prop_assert!(knapsack_weight(&modified_greedy(&items, weight_limit)) <= weight_limit);
+        for strategy in [GreedyStrategy::WeightProfitRatio, GreedyStrategy::Profit, GreedyStrategy::Weight] {
+            prop_assert!(
+                knapsack_weight(&integer_greedy_with_strategy(&items, weight_limit, strategy)) <= weight_limit
+            );
+        }
+        let fractional_weight: Fraction = fractional_greedy(&items, weight_limit)
+            .iter()
+            .map(PartialPackedItem::effective_weight)
+            .sum();
+        prop_assert!(fractional_weight <= Fraction::from(weight_limit));
+    }
+}
@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use aud2::knapsack::{SolveOptions, SolveStatus};
+use aud2::partition::*;
+
+/// Returns the smallest difference of any split of `numbers` into two parts by enumerating all subsets.
+fn brute_force_difference(numbers: &[u64]) -> u64 {
+    let total: u64 = numbers.iter().sum();
+    (0..1u32 << numbers.len())
+        .map(|mask| {
+            let sum: u64 = (0..numbers.len())
+                .filter(|i| mask & (1 << i) != 0)
+                .map(|i| numbers[i])
+                .sum();
+            sum.abs_diff(total - sum)
+        })
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn test_balanced_partition() {
+    let instances: [&[u64]; 6] = [
+        &[],
+        &[5],
+        &[3, 1, 1, 2, 2, 1],
+        &[7, 13, 17, 20, 29, 31, 31, 35, 57],
+        &[100, 1, 2, 3],
+        &[4, 4, 4, 0, 9, 11, 23, 2],
+    ];
+    for numbers in instances {
+        let partition = balanced_partition(numbers).unwrap();
+        // Each number is in exactly one part
+        let mut indices: Vec<usize> = partition.parts.concat();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..numbers.len()).collect::<Vec<_>>());
+        assert_eq!(partition, Partition::new(numbers, partition.parts.clone()));
+
+        let expected_difference = brute_force_difference(numbers);
+        assert_eq!(partition.difference(), expected_difference);
+        assert_eq!(can_partition(numbers), Ok(expected_difference == 0));
+    }
+}
+
+#[test]
+fn test_partition_sum_overflow() {
+    let numbers = [u64::MAX, 1, 2];
+    assert_eq!(balanced_partition(&numbers), Err(SumOverflowError));
+    assert_eq!(can_partition(&numbers), Err(SumOverflowError));
+    assert_eq!(karmarkar_karp(&numbers, 2), Err(SumOverflowError));
+    assert_eq!(
+        complete_karmarkar_karp(&numbers, 2, &SolveOptions::default()),
+        Err(SumOverflowError)
+    );
+}
+
+/// Returns the smallest difference of any split of `numbers` into `k` parts by enumerating all assignments.
+fn brute_force_difference_k(numbers: &[u64], k: usize) -> u64 {
+    let assignments = (k as u32).pow(numbers.len() as u32);
+    (0..assignments)
+        .map(|mut assignment| {
+            let mut sums = vec![0; k];
+            for number in numbers {
+                sums[assignment as usize % k] += number;
+                assignment /= k as u32;
+            }
+            sums.iter().max().unwrap() - sums.iter().min().unwrap()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Asserts that `partition` contains each index of `numbers` exactly once in `k` parts.
+fn assert_valid_partition(partition: &Partition, numbers: &[u64], k: usize) {
+    assert_eq!(partition.parts.len(), k);
+    let mut indices: Vec<usize> = partition.parts.concat();
+    indices.sort_unstable();
+    assert_eq!(indices, (0..numbers.len()).collect::<Vec<_>>());
+    assert_eq!(partition, &Partition::new(numbers, partition.parts.clone()));
+}
+
+#[test]
+fn test_karmarkar_karp() {
+    let numbers = [7, 13, 17, 20, 29, 31, 31, 35, 57];
+    for k in 1..=4 {
+        let heuristic = karmarkar_karp(&numbers, k).unwrap();
+        assert_valid_partition(&heuristic, &numbers, k);
+
+        let result = complete_karmarkar_karp(&numbers, k, &SolveOptions::default()).unwrap();
+        assert_eq!(result.status, SolveStatus::Completed);
+        assert_valid_partition(&result.partition, &numbers, k);
+        assert!(result.partition.difference() <= heuristic.difference());
+        assert_eq!(
+            result.partition.difference(),
+            brute_force_difference_k(&numbers, k)
+        );
+    }
+    assert_eq!(karmarkar_karp(&[], 3).unwrap().sums, vec![0, 0, 0]);
+}
+
+#[test]
+fn test_complete_karmarkar_karp_deadline() {
+    let numbers: Vec<u64> = (1..=40).map(|i| i * i * 7919 % 100_003).collect();
+    let options = SolveOptions {
+        deadline: Some(Instant::now()),
+        ..SolveOptions::default()
+    };
+    let result = complete_karmarkar_karp(&numbers, 3, &options).unwrap();
+    // Stopped before the first node, so the partition of the heuristic is returned
+    assert_eq!(result.status, SolveStatus::DeadlineExceeded);
+    assert_eq!(result.partition, karmarkar_karp(&numbers, 3).unwrap());
+}
+
+#[test]
+fn test_complete_karmarkar_karp_deadline_many_parts() {
+    let numbers: Vec<u64> = (1000..1038).collect();
+    let start = Instant::now();
+    let options = SolveOptions {
+        deadline: Some(start + Duration::from_millis(100)),
+        ..SolveOptions::default()
+    };
+    let result = complete_karmarkar_karp(&numbers, MAX_COMPLETE_PARTS, &options).unwrap();
+    // The children of a node are generated one at a time, so the deadline is checked regularly
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(result.status, SolveStatus::DeadlineExceeded);
+    assert_valid_partition(&result.partition, &numbers, MAX_COMPLETE_PARTS);
+}
@@ -0,0 +1,66 @@
+"""Tests for the Python bindings of aud2. Build the extension first, e.g. with `maturin develop`, then run `pytest`."""
+
+from fractions import Fraction
+
+import pytest
+
+import aud2
+
+ITEMS = [
+    {"id": 1, "profit": 3, "weight": 20},
+    {"id": 2, "profit": 3, "weight": 32},
+    {"id": 3, "profit": 10, "weight": 40},
+    {"id": 4, "profit": 5, "weight": 8},
+]
+
+
+def ids(solution):
+    return sorted(item["id"] for item in solution["items"])
+
+
+@pytest.mark.parametrize("solver", [aud2.dynamic_programming, aud2.branch_and_bound])
+def test_exact_solvers(solver):
+    solution = solver(ITEMS, 60)
+    assert ids(solution) == [3, 4]
+    assert solution["profit"] == 15
+    assert solution["weight"] == 48
+    assert solution["weight_limit"] == 60
+    assert {"id": 3, "profit": 10, "weight": 40} in solution["items"]
+
+
+def test_greedy_k():
+    assert ids(aud2.greedy_k(ITEMS, 60, 2)) == [3, 4]
+    assert aud2.greedy_k(ITEMS, 60, 0)["weight"] <= 60
+
+
+def test_integer_greedy():
+    solution = aud2.integer_greedy(ITEMS, 60)
+    assert solution["weight"] <= 60
+    assert solution["profit"] == sum(item["profit"] for item in solution["items"])
+
+
+def test_fractional_greedy():
+    result = aud2.fractional_greedy(ITEMS, 60)
+    take_ratios = {packed["item"]["id"]: packed["take_ratio"] for packed in result["items"]}
+    assert take_ratios == {4: Fraction(1), 3: Fraction(1), 1: Fraction(3, 5)}
+    assert result["profit"] == Fraction(84, 5)
+
+
+@pytest.mark.parametrize("solver", [aud2.subset_sum_set, aud2.subset_sum_vec])
+def test_subset_sum(solver):
+    assert solver([3, 5, 9], 14) == {
+        "numbers": [3, 5, 9],
+        "sum": 14,
+        "reachable": True,
+        "witness": [1, 2],
+    }
+    assert solver([3, 5, 9], 13)["reachable"] is False
+    assert solver([3, 5, 9], 13)["witness"] is None
+    assert solver([], 0)["witness"] == []
+
+
+def test_invalid_items():
+    with pytest.raises(KeyError):
+        aud2.dynamic_programming([{"id": 1, "profit": 3}], 10)
+    with pytest.raises(OverflowError):
+        aud2.dynamic_programming([{"id": 1, "profit": -3, "weight": 1}], 10)
@@ -1,3 +1,5 @@
+use std::num::NonZeroU128;
+
 use aud2::subset_sum::*;
 
 /// Numbers that can be used to produce sums.
@@ -37,3 +39,233 @@ fn test_subset_sum_set() {
 fn test_subset_sum_vec() {
     test_subset_sum(subset_sum_vec);
 }
+
+#[test]
+fn test_subset_sum_bitset() {
+    test_subset_sum(subset_sum_bitset);
+}
+
+type SubsetSumWitnessAlgorithm = fn(numbers: &[u64], limit: u64) -> Option<Vec<usize>>;
+
+/// Tests that a witness algorithm returns distinct indices summing up to each reachable sum.
+fn test_subset_sum_witness(algorithm: SubsetSumWitnessAlgorithm) {
+    let max_reachable = *EXPECTED_REACHABLE_SUMS.iter().max().unwrap();
+    for number in 0..=max_reachable {
+        let witness = algorithm(&NUMBERS, number);
+        assert_eq!(EXPECTED_REACHABLE_SUMS.contains(&number), witness.is_some());
+        if let Some(witness) = witness {
+            assert!(witness.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(witness.iter().map(|&i| NUMBERS[i]).sum::<u64>(), number);
+        }
+    }
+}
+
+#[test]
+fn test_subset_sum_set_witness() {
+    test_subset_sum_witness(subset_sum_set_witness);
+}
+
+#[test]
+fn test_subset_sum_vec_witness() {
+    test_subset_sum_witness(subset_sum_vec_witness);
+}
+
+#[test]
+fn test_subset_sum_bitset_witness() {
+    test_subset_sum_witness(subset_sum_bitset_witness);
+}
+
+#[test]
+fn test_subset_sum_bitset_word_boundaries() {
+    // Numbers and sums around the boundaries of the u64 words
+    let numbers = [1, 62, 63, 64, 65, 127, 128, 200];
+    for sum in 0..=numbers.iter().sum::<u64>() + 64 {
+        assert_eq!(
+            subset_sum_bitset(&numbers, sum),
+            subset_sum_vec(&numbers, sum)
+        );
+    }
+}
+
+#[test]
+fn test_subset_sum_count() {
+    let max_reachable = *EXPECTED_REACHABLE_SUMS.iter().max().unwrap();
+    let counts = subset_sum_count(&NUMBERS, max_reachable, None).unwrap();
+    // Count the subsets by enumerating all bitmasks over NUMBERS
+    let mut expected_counts = vec![0u128; max_reachable as usize + 1];
+    for mask in 0..1u32 << NUMBERS.len() {
+        let sum: u64 = (0..NUMBERS.len())
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| NUMBERS[i])
+            .sum();
+        expected_counts[sum as usize] += 1;
+    }
+    assert_eq!(counts, expected_counts);
+
+    let modulus = NonZeroU128::new(3).unwrap();
+    let counts_modulo = subset_sum_count(&NUMBERS, max_reachable, Some(modulus)).unwrap();
+    let expected_counts_modulo: Vec<u128> = expected_counts.iter().map(|count| count % 3).collect();
+    assert_eq!(counts_modulo, expected_counts_modulo);
+}
+
+#[test]
+fn test_subset_sum_count_overflow() {
+    // Each 0 doubles the number of subsets with sum 0, so 2^128 overflows
+    let zeros = [0; 128];
+    assert_eq!(
+        subset_sum_count(&zeros, 0, None),
+        Err(CountOverflowError { sum: 0 })
+    );
+    let modulus = NonZeroU128::new(1_000_000_007).unwrap();
+    assert_eq!(
+        subset_sum_count(&zeros, 0, Some(modulus)),
+        Ok(vec![(0..128).fold(1, |count, _| count * 2 % 1_000_000_007)])
+    );
+}
+
+type SubsetSumSignedAlgorithm = fn(numbers: &[i64], target: i64) -> Option<Vec<usize>>;
+
+/// Tests a subset sum algorithm with negative numbers against all subsets.
+fn test_subset_sum_signed(algorithm: SubsetSumSignedAlgorithm) {
+    let numbers: [i64; 8] = [-20, 7, -13, 31, 0, -5, 17, 31];
+    let sums: Vec<i64> = (0..1u32 << numbers.len())
+        .map(|mask| {
+            (0..numbers.len())
+                .filter(|i| mask & (1 << i) != 0)
+                .map(|i| numbers[i])
+                .sum()
+        })
+        .collect();
+    for target in -50..=100 {
+        let witness = algorithm(&numbers, target);
+        assert_eq!(sums.contains(&target), witness.is_some());
+        if let Some(witness) = witness {
+            assert!(witness.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(witness.iter().map(|&i| numbers[i]).sum::<i64>(), target);
+        }
+    }
+    // Only non-negative numbers behave like the unsigned algorithms
+    let unsigned_numbers: Vec<i64> = NUMBERS.iter().map(|&number| number as i64).collect();
+    for target in 0..=240 {
+        assert_eq!(
+            algorithm(&unsigned_numbers, target).is_some(),
+            EXPECTED_REACHABLE_SUMS.contains(&(target as u64))
+        );
+    }
+}
+
+#[test]
+fn test_subset_sum_signed_set() {
+    test_subset_sum_signed(subset_sum_signed_set);
+}
+
+#[test]
+fn test_subset_sum_signed_vec() {
+    test_subset_sum_signed(subset_sum_signed_vec);
+}
+
+#[test]
+fn test_approximate_subset_sum() {
+    for epsilon in [0.5, 0.1, 0.01] {
+        for target in 0..=260 {
+            let subset = approximate_subset_sum(&NUMBERS, target, epsilon);
+            let best = *EXPECTED_REACHABLE_SUMS
+                .iter()
+                .filter(|&&sum| sum <= target)
+                .max()
+                .unwrap();
+            assert!(subset.sum <= target);
+            assert!(subset.sum as f64 >= (1.0 - epsilon) * best as f64);
+            assert!(subset.indices.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(
+                subset.indices.iter().map(|&i| NUMBERS[i]).sum::<u64>(),
+                subset.sum
+            );
+        }
+    }
+    // Large numbers, whose sums would not fit into a table
+    let numbers = [u64::MAX / 3, u64::MAX / 4, u64::MAX / 5, 1 << 60];
+    let subset = approximate_subset_sum(&numbers, u64::MAX / 2, 0.1);
+    assert!(subset.sum <= u64::MAX / 2);
+    assert!(subset.sum as f64 >= 0.9 * (u64::MAX / 4 + u64::MAX / 5) as f64);
+}
+
+type ClosestSubsetSumAlgorithm = fn(numbers: &[u64], target: u64) -> Subset;
+
+/// Tests that a closest subset sum algorithm returns a valid subset with the sum chosen by `expected_sum` from the
+/// reachable sums and the target.
+fn test_closest_subset_sum(
+    algorithm: ClosestSubsetSumAlgorithm,
+    expected_sum: fn(&[u64], u64) -> u64,
+) {
+    for target in 0..=260 {
+        let subset = algorithm(&NUMBERS, target);
+        assert_eq!(subset.sum, expected_sum(&EXPECTED_REACHABLE_SUMS, target));
+        assert!(subset.indices.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            subset.indices.iter().map(|&i| NUMBERS[i]).sum::<u64>(),
+            subset.sum
+        );
+    }
+}
+
+/// The largest reachable sum not exceeding `target`.
+fn largest_at_most(reachable_sums: &[u64], target: u64) -> u64 {
+    *reachable_sums
+        .iter()
+        .filter(|&&sum| sum <= target)
+        .max()
+        .unwrap()
+}
+
+/// The reachable sum nearest to `target`, preferring the smaller one on ties.
+fn nearest(reachable_sums: &[u64], target: u64) -> u64 {
+    *reachable_sums
+        .iter()
+        .min_by_key(|&&sum| (sum.abs_diff(target), sum))
+        .unwrap()
+}
+
+#[test]
+fn test_closest_subset_sum_set() {
+    test_closest_subset_sum(closest_subset_sum_set, largest_at_most);
+    test_closest_subset_sum(nearest_subset_sum_set, nearest);
+}
+
+#[test]
+fn test_closest_subset_sum_vec() {
+    test_closest_subset_sum(closest_subset_sum_vec, largest_at_most);
+    test_closest_subset_sum(nearest_subset_sum_vec, nearest);
+}
+
+#[test]
+fn test_subset_sum_cardinality() {
+    // The sizes of all subsets reaching each sum
+    let max_reachable = *EXPECTED_REACHABLE_SUMS.iter().max().unwrap();
+    let mut sizes = vec![Vec::new(); max_reachable as usize + 1];
+    for mask in 0..1u32 << NUMBERS.len() {
+        let sum: u64 = (0..NUMBERS.len())
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| NUMBERS[i])
+            .sum();
+        sizes[sum as usize].push(mask.count_ones() as usize);
+    }
+
+    for (min_count, max_count) in [(0, 9), (3, 3), (0, 2), (4, 6), (5, 2)] {
+        for target in 0..=max_reachable {
+            let witness = subset_sum_cardinality(&NUMBERS, target, min_count..=max_count);
+            let fewest = sizes[target as usize]
+                .iter()
+                .filter(|&&size| (min_count..=max_count).contains(&size))
+                .min();
+            assert_eq!(witness.as_ref().map(Vec::len), fewest.copied());
+            if let Some(witness) = witness {
+                assert!(witness.windows(2).all(|pair| pair[0] < pair[1]));
+                assert_eq!(witness.iter().map(|&i| NUMBERS[i]).sum::<u64>(), target);
+            }
+        }
+    }
+    assert_eq!(subset_sum_cardinality(&NUMBERS, 0, ..), Some(vec![]));
+    assert_eq!(subset_sum_cardinality(&NUMBERS, 0, 1..), None);
+    assert_eq!(subset_sum_cardinality(&NUMBERS, 7, ..1), None);
+}